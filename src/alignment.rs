@@ -0,0 +1,216 @@
+use std::{cmp::Reverse, fs, ops::RangeInclusive, sync::OnceLock};
+
+const BUILT_IN_ALIGNMENT: &str = include_str!("../data/alignment.txt");
+
+static ALIGNMENT: OnceLock<AlignmentTable> = OnceLock::new();
+
+/// How the districts of one conference are grouped into regions in one year
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Alignment {
+    pub regions: u8,
+    pub districts_per_region: u8,
+}
+
+impl Alignment {
+    /// For conferences the table has no rows for, which have no districts or regions
+    const EMPTY: Self = Self {
+        regions: 0,
+        districts_per_region: 0,
+    };
+
+    pub const fn regions(&self) -> RangeInclusive<u8> {
+        1..=self.regions
+    }
+
+    /// Every district, or only the districts in `region` (0 for all regions).
+    /// None if the conference has no such region.
+    pub fn districts(&self, region: Option<u8>) -> Option<RangeInclusive<u8>> {
+        match region {
+            Some(region) if region != 0 => {
+                if region > self.regions {
+                    return None;
+                }
+                let last = region * self.districts_per_region;
+                Some(last - self.districts_per_region + 1..=last)
+            }
+            _ => Some(1..=self.regions * self.districts_per_region),
+        }
+    }
+
+    pub fn region_of(&self, district: u8) -> Option<u8> {
+        if district == 0 || self.districts_per_region == 0 {
+            return None;
+        }
+        let region = (district - 1) / self.districts_per_region + 1;
+        if region > self.regions {
+            return None;
+        }
+        Some(region)
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct AlignmentTable {
+    rows: Vec<(RangeInclusive<u16>, RangeInclusive<u8>, Alignment)>,
+}
+
+impl AlignmentTable {
+    pub fn built_in() -> Self {
+        let mut table = Self::default();
+        table
+            .add(BUILT_IN_ALIGNMENT)
+            .expect("Built-in alignment table is malformed");
+        table
+    }
+
+    /// Adds the rows from an alignment file, with later rows taking priority
+    pub fn add(&mut self, text: &str) -> Result<(), String> {
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = || {
+                format!(
+                    "line {}: expected `<years> <conferences> <regions> <districts per region>`",
+                    number + 1
+                )
+            };
+
+            let columns: Vec<&str> = line.split_whitespace().collect();
+            if columns.len() != 4 {
+                return Err(error());
+            }
+            let years = parse_range::<u16>(columns[0]).ok_or_else(error)?;
+            let conferences = parse_range::<u8>(columns[1]).ok_or_else(error)?;
+            let alignment = Alignment {
+                regions: columns[2].parse().map_err(|_| error())?,
+                districts_per_region: columns[3].parse().map_err(|_| error())?,
+            };
+            if alignment
+                .regions
+                .checked_mul(alignment.districts_per_region)
+                .is_none()
+            {
+                return Err(format!("line {}: too many districts", number + 1));
+            }
+            self.rows.push((years, conferences, alignment));
+        }
+        Ok(())
+    }
+
+    /// The alignment for a season, or for seasons after the last cycle in the table,
+    /// the most recent one known for the conference
+    pub fn get(&self, year: u16, conference: u8) -> Option<Alignment> {
+        let rows = || {
+            self.rows
+                .iter()
+                .rev()
+                .filter(|(_, conferences, _)| conferences.contains(&conference))
+        };
+        rows()
+            .find(|(years, _, _)| years.contains(&year))
+            .or_else(|| {
+                rows()
+                    .filter(|(years, _, _)| *years.end() < year)
+                    .min_by_key(|(years, _, _)| Reverse(*years.end()))
+            })
+            .map(|(_, _, alignment)| *alignment)
+    }
+}
+
+pub fn parse_range<T: std::str::FromStr + Copy>(string: &str) -> Option<RangeInclusive<T>> {
+    match string.split_once('-') {
+        Some((start, end)) => Some(start.parse().ok()?..=end.parse().ok()?),
+        None => {
+            let value = string.parse().ok()?;
+            Some(value..=value)
+        }
+    }
+}
+
+/// Loads the built-in alignment plus the rows in `path`, if given.
+/// Has to be called before anything is scraped for the extra rows to apply.
+pub fn load(path: Option<&str>) -> Result<(), String> {
+    let mut table = AlignmentTable::built_in();
+    if let Some(path) = path {
+        let text = fs::read_to_string(path).map_err(|err| format!("{path}: {err}"))?;
+        table.add(&text).map_err(|err| format!("{path}: {err}"))?;
+    }
+    ALIGNMENT
+        .set(table)
+        .map_err(|_| String::from("the alignment table was already loaded"))
+}
+
+pub fn alignment(year: u16, conference: u8) -> Alignment {
+    ALIGNMENT
+        .get_or_init(AlignmentTable::built_in)
+        .get(year, conference)
+        .unwrap_or(Alignment::EMPTY)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIX_A: Alignment = Alignment {
+        regions: 4,
+        districts_per_region: 8,
+    };
+
+    fn table(text: &str) -> AlignmentTable {
+        let mut table = AlignmentTable::default();
+        table.add(text).unwrap();
+        table
+    }
+
+    #[test]
+    fn districts_of_a_region() {
+        assert_eq!(SIX_A.districts(None), Some(1..=32));
+        assert_eq!(SIX_A.districts(Some(0)), Some(1..=32));
+        assert_eq!(SIX_A.districts(Some(1)), Some(1..=8));
+        assert_eq!(SIX_A.districts(Some(4)), Some(25..=32));
+    }
+
+    #[test]
+    fn unknown_regions_have_no_districts() {
+        assert_eq!(SIX_A.districts(Some(5)), None);
+        assert_eq!(Alignment::EMPTY.districts(Some(1)), None);
+    }
+
+    #[test]
+    fn region_of_at_region_boundaries() {
+        assert_eq!(SIX_A.region_of(1), Some(1));
+        assert_eq!(SIX_A.region_of(8), Some(1));
+        assert_eq!(SIX_A.region_of(9), Some(2));
+        assert_eq!(SIX_A.region_of(32), Some(4));
+        assert_eq!(SIX_A.region_of(33), None);
+        assert_eq!(SIX_A.region_of(0), None);
+        assert_eq!(Alignment::EMPTY.region_of(1), None);
+    }
+
+    #[test]
+    fn later_rows_win() {
+        let table = table("2014-2025 1-6 4 8\n2024-2025 6 4 4\n");
+        assert_eq!(table.get(2024, 6).map(|a| a.districts_per_region), Some(4));
+        assert_eq!(table.get(2024, 5), Some(SIX_A));
+        assert_eq!(table.get(2013, 6), None);
+    }
+
+    #[test]
+    fn later_seasons_use_the_last_cycle() {
+        let table = table("2020-2021 6 4 8\n2022-2025 6 4 7\n2022-2025 6 4 6\n");
+        assert_eq!(table.get(2030, 6).map(|a| a.districts_per_region), Some(6));
+    }
+
+    #[test]
+    fn malformed_rows_are_rejected() {
+        let mut table = AlignmentTable::default();
+        assert!(table.add("2014-2025 1-6 4\n").is_err());
+        assert!(table.add("2014-2025 1-6 four 8\n").is_err());
+        assert_eq!(
+            table.add("# too big\n2014 1 200 200\n"),
+            Err(String::from("line 2: too many districts"))
+        );
+    }
+}
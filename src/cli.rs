@@ -1,11 +1,16 @@
 use clap::Parser;
 use clap::Subcommand;
 
+use crate::competitor::{MatchRule, Matching};
+use crate::output::{Column, Format, GroupBy};
+use crate::sort::SortKey;
+use crate::theme::ColorChoice;
+
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
 pub struct Cli {
-    /// Subject to find
-    pub subject: String,
+    /// Subject to find (not needed for `history`)
+    pub subject: Option<String>,
 
     /// Find a specific conference, a range of conferences (ex. 3-5), or ignore for all conferences
     #[arg(short, long, value_name = "CONFERENCE")]
@@ -45,14 +50,100 @@ pub struct Cli {
     #[arg(short, long)]
     pub mute: bool,
 
+    /// Lists the pages the results were scraped from, and when
+    #[arg(long)]
+    pub sources: bool,
+
+    /// Shows the scores that decided who advanced from each district or region instead of the results
+    #[arg(long, conflicts_with_all = ["stats", "strength", "project", "tui"])]
+    pub cutoffs: bool,
+
+    /// Shows the count, mean, median, spread and a histogram of the scores, overall and
+    /// for each conference and district, instead of the results
+    #[arg(long, conflicts_with_all = ["strength", "project", "tui"])]
+    pub stats: bool,
+
+    /// Ranks the districts and regions of each conference by how competitive they were,
+    /// instead of the results
+    #[arg(long, conflicts_with_all = ["project", "tui"])]
+    pub strength: bool,
+
+    /// Projects the next meet up (region from district results, state from region results)
+    /// from who advanced
+    #[arg(long, conflicts_with_all = ["tui"])]
+    pub project: bool,
+
+    /// Browses the results in an interactive terminal UI
+    #[arg(long)]
+    pub tui: bool,
+
     /// Shows the highest scores across all conferences for the specified subject
     #[arg(long)]
     pub highscores: bool,
 
+    /// Decides which fields have to agree for results to be merged into one competitor
+    #[arg(long, value_enum, value_name = "RULE", default_value_t = MatchRule::NameSchool)]
+    pub match_by: MatchRule,
+
+    /// Ignores middle names and initials when merging competitors
+    #[arg(long)]
+    pub ignore_middle_names: bool,
+
+    /// Adds school name aliases from a file (same format as data/school_aliases.txt)
+    #[arg(long, value_name = "FILE")]
+    pub aliases: Option<String>,
+
+    /// Adds district/region alignment rows from a file (same format as data/alignment.txt)
+    #[arg(long, value_name = "FILE")]
+    pub alignment: Option<String>,
+
+    /// What to order tables and exports by, e.g. `conference,score` (places are kept)
+    #[arg(long, value_enum, value_delimiter = ',', global = true)]
+    pub sort: Vec<SortKey>,
+
+    /// Which fields tables and exports show, e.g. `place,name,school,score`
+    #[arg(long, value_enum, value_delimiter = ',', global = true)]
+    pub columns: Vec<Column>,
+
+    /// Shows each district, region or conference separately, with a line under the last advancing rows
+    #[arg(long, value_enum, global = true)]
+    pub group_by: Option<GroupBy>,
+
+    /// How to print the results: colored tables, JSON, JSON Lines, CSV files, Markdown, or HTML
+    #[arg(long, value_enum, default_value_t = Format::Text, global = true)]
+    pub format: Format,
+
+    /// When to color the output: when the terminal supports it, always, or never
+    #[arg(long, value_enum, default_value_t = ColorChoice::Auto, global = true)]
+    pub color: ColorChoice,
+
+    /// Adds conference colors, medal colors and advance labels from a file (same format as data/theme.txt)
+    #[arg(long, value_name = "FILE", global = true)]
+    pub theme: Option<String>,
+
+    /// Prints one uncolored line per result with every field labeled, for screen readers
+    /// and simple log parsers
+    #[arg(long, global = true)]
+    pub plain: bool,
+
+    /// Directory to write exported files to. CSV files default to the current directory;
+    /// Markdown and HTML are printed unless this is given
+    #[arg(long, value_name = "DIR", global = true)]
+    pub output: Option<String>,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
+impl Cli {
+    pub const fn matching(&self) -> Matching {
+        Matching {
+            rule: self.match_by,
+            ignore_middle_names: self.ignore_middle_names,
+        }
+    }
+}
+
 #[derive(Subcommand, Clone)]
 pub enum Commands {
     Compare {
@@ -68,4 +159,46 @@ pub enum Commands {
         #[arg(short, long)]
         state: bool,
     },
+    /// Follows one competitor through every subject, level and season
+    History {
+        /// The competitor's name
+        name: String,
+        /// Only count results from this school
+        #[arg(long)]
+        school: Option<String>,
+        /// Seasons to look through, like 2022-2025, or the last four
+        #[arg(long, value_name = "YEARS")]
+        years: Option<String>,
+        /// Only look in these conferences, like 5 or 4-6
+        #[arg(short, long)]
+        conferences: Option<String>,
+    },
+
+    /// Profile one school's season: every event at every level it reached
+    School {
+        /// The school's name
+        name: String,
+        /// The season to profile, or leave blank for the current year
+        #[arg(short, long, value_name = "YEAR")]
+        year: Option<u16>,
+        /// Only look in these conferences, like 5 or 4-6
+        #[arg(short, long)]
+        conferences: Option<String>,
+    },
+
+    /// Shows how a subject, or one school in it, changed over several seasons
+    Trend {
+        /// Only count this school's results
+        #[arg(long)]
+        school: Option<String>,
+        /// Seasons to look through, like 2018-2024, or the last four
+        #[arg(long, value_name = "YEARS")]
+        years: Option<String>,
+        #[arg(short, long)]
+        district: bool,
+        #[arg(short, long)]
+        region: bool,
+        #[arg(short, long)]
+        state: bool,
+    },
 }
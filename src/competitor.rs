@@ -0,0 +1,116 @@
+use clap::ValueEnum;
+
+use crate::{individual::Individual, school, team::Team};
+
+/// Which fields have to agree for two result rows to count as the same competitor
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum MatchRule {
+    /// Only the name has to match (students with the same name get merged)
+    Name,
+    /// The name and the school have to match
+    #[default]
+    NameSchool,
+    /// The name, school and conference have to match
+    NameSchoolConference,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Matching {
+    pub rule: MatchRule,
+    /// Only compare the first and last parts of a name, so "Justin Q. Nguyen" matches "Justin Nguyen"
+    pub ignore_middle_names: bool,
+}
+
+impl Matching {
+    /// Schools are always told apart by their name, even when individuals are matched by name only
+    const fn for_schools(self) -> Self {
+        let rule = match self.rule {
+            MatchRule::Name => MatchRule::NameSchool,
+            rule => rule,
+        };
+        Self { rule, ..self }
+    }
+}
+
+/// Identity of a competitor (or a school, for teams) that is stable across meets and years
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CompetitorKey {
+    pub name: String,
+    pub school: String,
+    pub conference: u8,
+}
+
+impl CompetitorKey {
+    pub fn individual(individual: &Individual, matching: Matching) -> Self {
+        Self::new(
+            &individual.name,
+            &individual.school,
+            individual.conference,
+            matching,
+        )
+    }
+
+    pub fn team(team: &Team, matching: Matching) -> Self {
+        Self::new("", &team.school, team.conference, matching.for_schools())
+    }
+
+    /// The key of the team that an individual competes for
+    pub fn team_of(individual: &Individual, matching: Matching) -> Self {
+        Self::new(
+            "",
+            &individual.school,
+            individual.conference,
+            matching.for_schools(),
+        )
+    }
+
+    fn new(name: &str, school: &str, conference: u8, matching: Matching) -> Self {
+        let name = normalize_name(name, matching.ignore_middle_names);
+        let school = match matching.rule {
+            MatchRule::Name => String::new(),
+            _ => normalize_school(school),
+        };
+        let conference = match matching.rule {
+            MatchRule::NameSchoolConference => conference,
+            _ => 0,
+        };
+
+        Self {
+            name,
+            school,
+            conference,
+        }
+    }
+}
+
+/// Lowercases a name, drops punctuation and sorts the words so that
+/// "Nguyen, Justin" and "Justin Nguyen" become the same string
+pub fn normalize_name(name: &str, ignore_middle_names: bool) -> String {
+    let mut words = split_words(name);
+
+    if ignore_middle_names && words.len() > 2 {
+        let last = words.pop().unwrap_or_default();
+        words.truncate(1);
+        words.push(last);
+    }
+
+    words.sort();
+    words.join(" ")
+}
+
+pub fn normalize_school(school: &str) -> String {
+    school::key(school)
+}
+
+fn split_words(string: &str) -> Vec<String> {
+    string
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '&')
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
@@ -0,0 +1,94 @@
+use std::{ops::RangeInclusive, sync::OnceLock};
+
+use crate::alignment::parse_range;
+
+const BUILT_IN_CONFERENCES: &str = include_str!("../data/conferences.txt");
+
+static CONFERENCES: OnceLock<Vec<(RangeInclusive<u16>, Vec<Conference>)>> = OnceLock::new();
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Conference {
+    /// The number sent in requests
+    pub code: u8,
+    pub label: String,
+}
+
+fn table() -> &'static Vec<(RangeInclusive<u16>, Vec<Conference>)> {
+    CONFERENCES.get_or_init(|| {
+        let mut rows = Vec::new();
+        for line in BUILT_IN_CONFERENCES.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut columns = line.split_whitespace();
+            let years = columns
+                .next()
+                .and_then(parse_range::<u16>)
+                .expect("Built-in conference table is malformed");
+            let conferences = columns
+                .map(|column| {
+                    let (code, label) = column.split_once('=').unwrap_or((column, ""));
+                    let code = code
+                        .parse::<u8>()
+                        .expect("Built-in conference table is malformed");
+                    let label = if label.is_empty() {
+                        format!("{code}A")
+                    } else {
+                        label.to_string()
+                    };
+                    Conference { code, label }
+                })
+                .collect();
+            rows.push((years, conferences));
+        }
+        rows
+    })
+}
+
+/// Every conference that existed in `year`
+pub fn conferences(year: u16) -> Vec<Conference> {
+    table()
+        .iter()
+        .rev()
+        .find(|(years, _)| years.contains(&year))
+        .map(|(_, conferences)| conferences.clone())
+        .unwrap_or_default()
+}
+
+pub fn exists(year: u16, code: u8) -> bool {
+    conferences(year)
+        .iter()
+        .any(|conference| conference.code == code)
+}
+
+/// Every conference code that existed in any year
+pub fn all_codes() -> Vec<u8> {
+    let mut codes: Vec<u8> = table()
+        .iter()
+        .flat_map(|(_, conferences)| conferences.iter().map(|conference| conference.code))
+        .collect();
+    codes.sort();
+    codes.dedup();
+    codes
+}
+
+/// The name of a conference, like "5A"
+pub fn label(year: u16, code: u8) -> String {
+    conferences(year)
+        .into_iter()
+        .find(|conference| conference.code == code)
+        .map(|conference| conference.label)
+        .unwrap_or(format!("{code}A"))
+}
+
+/// Drops the conferences that didn't exist in `year`, returning the ones that were dropped
+pub fn retain_existing(year: u16, conferences: &mut Vec<u8>) -> Vec<u8> {
+    let missing: Vec<u8> = conferences
+        .iter()
+        .copied()
+        .filter(|code| !exists(year, *code))
+        .collect();
+    conferences.retain(|code| exists(year, *code));
+    missing
+}
@@ -0,0 +1,140 @@
+use serde::Serialize;
+
+use crate::{
+    advance::{AdvanceTypeIndividual, AdvanceTypeTeam},
+    cli::Cli,
+    output::{GroupBy, Report, emit_summary, optional, print_aligned},
+    request::district_as_region,
+    score::Score,
+    team::Team,
+};
+
+/// The scores that decided who advanced from one meet
+#[derive(Clone, Debug, Serialize)]
+pub struct Cutoff {
+    pub meet: String,
+    /// The lowest individual score that advanced by placing
+    pub lowest_advancing: Option<Score>,
+    /// The score of the team that won
+    pub winning_team: Option<Score>,
+    /// The score of the wildcard team, which every other runner-up team needed to beat
+    pub wildcard: Option<Score>,
+    /// The best score that didn't advance by placing: the highest score below
+    /// `lowest_advancing` of anyone not advancing as an individual. Someone who only
+    /// went on with their team still counts, since they missed the individual cutoff.
+    pub first_out: Option<Score>,
+}
+
+/// The group the wildcard is picked from: the region a district is in, or the
+/// whole conference for regions
+fn wildcard_group(team: &Team) -> Option<u8> {
+    match team.district {
+        Some(district) => district_as_region(Some(district), team.year, team.conference),
+        None => Some(1),
+    }
+}
+
+/// The cutoffs of every district or region meet in the report
+pub fn cutoffs(report: &Report) -> Vec<Cutoff> {
+    let by_district = report
+        .individuals
+        .iter()
+        .any(|individual| individual.district.is_some())
+        || report.teams.iter().any(|team| team.district.is_some());
+    let group_by = if by_district {
+        GroupBy::District
+    } else {
+        GroupBy::Region
+    };
+
+    report
+        .groups(group_by)
+        .into_iter()
+        .map(|(meet, group)| {
+            let lowest_advancing = group
+                .individuals
+                .iter()
+                .filter(|individual| individual.advance == Some(AdvanceTypeIndividual::Indiv))
+                .map(|individual| individual.score)
+                .min();
+            let first_out = group
+                .individuals
+                .iter()
+                .filter(|individual| individual.advance != Some(AdvanceTypeIndividual::Indiv))
+                .map(|individual| individual.score)
+                .filter(|score| lowest_advancing.is_none_or(|lowest| *score < lowest))
+                .max();
+            let winning_team = group.teams.iter().map(|team| team.score).max();
+            let wildcard = group.teams.first().and_then(|first| {
+                report
+                    .teams
+                    .iter()
+                    .find(|team| {
+                        team.advance == Some(AdvanceTypeTeam::Alternate)
+                            && team.conference == first.conference
+                            && wildcard_group(team) == wildcard_group(first)
+                    })
+                    .map(|team| team.score)
+            });
+
+            Cutoff {
+                meet,
+                lowest_advancing,
+                winning_team,
+                wildcard,
+                first_out,
+            }
+        })
+        .collect()
+}
+
+/// Prints the cutoffs as a table, or as JSON
+pub fn print(report: &Report, cli: &Cli) {
+    let cutoffs = cutoffs(report);
+    emit_summary("Cutoffs", &cutoffs, &cutoffs, cli, || {
+        print_table(report, &cutoffs, cli)
+    });
+}
+
+fn print_table(report: &Report, cutoffs: &[Cutoff], cli: &Cli) {
+    println!("{} Advancement Cutoffs:", report.subject.to_string());
+
+    if cli.plain {
+        for cutoff in cutoffs {
+            let mut fields = vec![cutoff.meet.clone()];
+            for (label, score) in [
+                ("lowest advancing", cutoff.lowest_advancing),
+                ("winning team", cutoff.winning_team),
+                ("wildcard", cutoff.wildcard),
+                ("first out", cutoff.first_out),
+            ] {
+                if let Some(score) = score {
+                    fields.push(format!("{label}: {score}"));
+                }
+            }
+            println!("{}", fields.join("; "));
+        }
+        return;
+    }
+
+    let headers = [
+        "Meet",
+        "Lowest Advancing",
+        "Winning Team",
+        "Wildcard",
+        "First Out",
+    ];
+    let rows: Vec<Vec<String>> = cutoffs
+        .iter()
+        .map(|cutoff| {
+            vec![
+                cutoff.meet.clone(),
+                optional(cutoff.lowest_advancing),
+                optional(cutoff.winning_team),
+                optional(cutoff.wildcard),
+                optional(cutoff.first_out),
+            ]
+        })
+        .collect();
+    print_aligned(&headers, &rows, 0);
+}
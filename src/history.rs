@@ -0,0 +1,274 @@
+use chrono::Datelike;
+use colored::{ColoredString, Colorize};
+
+use crate::{
+    advance::AdvanceTypeIndividual,
+    cli::Cli,
+    competitor::{CompetitorKey, normalize_name, normalize_school},
+    conference,
+    individual::Individual,
+    output::{Report, ordinal},
+    request::{Level, RequestFields, Subject},
+    score::Score,
+    scrape_meet,
+    theme::theme,
+};
+
+/// One result of the competitor being followed
+struct Entry {
+    year: u16,
+    subject: Subject,
+    level: Level,
+    conference: u8,
+    place: usize,
+    score: Score,
+    advance: Option<AdvanceTypeIndividual>,
+}
+
+/// Every result found for one person
+struct Person {
+    key: CompetitorKey,
+    name: String,
+    school: String,
+    entries: Vec<Entry>,
+}
+
+/// Adds every match from one meet to the person it belongs to
+fn record(
+    people: &mut Vec<Person>,
+    report: &Report,
+    found: &[Individual],
+    subject: &Subject,
+    cli: &Cli,
+) {
+    for individual in found {
+        let key = CompetitorKey::individual(individual, cli.matching());
+        let index = match people.iter().position(|person| person.key == key) {
+            Some(index) => index,
+            None => {
+                people.push(Person {
+                    key,
+                    name: individual.name.clone(),
+                    school: individual.school.clone(),
+                    entries: Vec::new(),
+                });
+                people.len() - 1
+            }
+        };
+        people[index].entries.push(Entry {
+            year: individual.year,
+            subject: subject.clone(),
+            level: individual.level,
+            conference: individual.conference,
+            place: report.individual_place(individual),
+            score: individual.score,
+            advance: individual.advance.clone(),
+        });
+    }
+}
+
+/// Finds a competitor's district results in every event and season, then follows
+/// them to region and state for as long as they kept advancing
+pub fn run(
+    name: &str,
+    school: Option<&str>,
+    years: Option<String>,
+    conferences: Option<String>,
+    cli: &Cli,
+) {
+    let current: u16 = chrono::Utc::now().year().try_into().unwrap_or(2004);
+    let years = match years {
+        Some(years) => match RequestFields::parse_years(&years) {
+            Some(years) => years,
+            None => {
+                eprintln!("{}", format!("Unknown years {years}").red());
+                return;
+            }
+        },
+        // Every season means scraping every district of every event, so only the
+        // last four are searched unless asked for more
+        None => current - 3..=current,
+    };
+    let conferences = match conferences {
+        Some(conferences) => match RequestFields::parse_range(conferences.clone()) {
+            Some(conferences) => conferences,
+            None => {
+                eprintln!("{}", format!("Unknown conference {conferences}").red());
+                return;
+            }
+        },
+        None => conference::all_codes(),
+    };
+
+    let matching = cli.matching();
+    let wanted_name = normalize_name(name, matching.ignore_middle_names);
+    let wanted_school = school.map(normalize_school);
+    // Matches like `--find` (the name or school contains it), or the full name in any order
+    let is_match = |individual: &Individual| {
+        (individual.matches(name)
+            || normalize_name(&individual.name, matching.ignore_middle_names) == wanted_name)
+            && wanted_school
+                .as_ref()
+                .is_none_or(|school| normalize_school(&individual.school) == *school)
+    };
+
+    let mut people: Vec<Person> = Vec::new();
+    for year in years {
+        for subject in Subject::events(year) {
+            let Some(report) =
+                scrape_meet(&subject, year, Level::District(0), conferences.clone(), cli)
+            else {
+                continue;
+            };
+            let mut found: Vec<Individual> = report
+                .individuals
+                .iter()
+                .filter(|individual| is_match(individual))
+                .cloned()
+                .collect();
+            record(&mut people, &report, &found, &subject, cli);
+
+            // Follow everyone who advanced up a level, until they stop advancing
+            while let Some(individual) = found.pop() {
+                if individual.advance.is_none() {
+                    continue;
+                }
+                let Some(next) =
+                    individual
+                        .level
+                        .next(individual.district, year, individual.conference)
+                else {
+                    continue;
+                };
+                let Some(report) =
+                    scrape_meet(&subject, year, next, vec![individual.conference], cli)
+                else {
+                    continue;
+                };
+                let key = CompetitorKey::individual(&individual, matching);
+                let advanced: Vec<Individual> = report
+                    .individuals
+                    .iter()
+                    .filter(|x| CompetitorKey::individual(x, matching) == key)
+                    .cloned()
+                    .collect();
+                record(&mut people, &report, &advanced, &subject, cli);
+                found.extend(advanced);
+            }
+        }
+    }
+
+    if people.is_empty() {
+        eprintln!("{}", format!("Couldn't find {name}").red());
+        return;
+    }
+    if people.len() > 1 && school.is_none() {
+        eprintln!(
+            "{}",
+            format!(
+                "Found {} people named {name}; use --school to pick one",
+                people.len()
+            )
+            .yellow()
+        );
+    }
+    for person in people.iter_mut() {
+        print(person, cli);
+    }
+}
+
+fn print(person: &mut Person, cli: &Cli) {
+    let theme = theme();
+    person.entries.sort_by(|a, b| {
+        (a.year, a.subject.to_string(), a.level).cmp(&(b.year, b.subject.to_string(), b.level))
+    });
+
+    println!("{} ({})", person.name.bold(), person.school);
+
+    let mut years: Vec<u16> = person.entries.iter().map(|entry| entry.year).collect();
+    years.dedup();
+    for year in years {
+        let entries: Vec<&Entry> = person
+            .entries
+            .iter()
+            .filter(|entry| entry.year == year)
+            .collect();
+        let reached = entries
+            .iter()
+            .map(|entry| entry.level)
+            .max()
+            .map(|level| match level {
+                Level::District(_) => "District",
+                Level::Region(_) => "Region",
+                Level::State => "State",
+            })
+            .unwrap_or_default();
+        let conference = entries
+            .first()
+            .map(|entry| conference::label(year, entry.conference))
+            .unwrap_or_default();
+
+        if cli.plain {
+            for entry in entries {
+                let mut fields = vec![
+                    year.to_string(),
+                    entry.subject.to_string().to_string(),
+                    format!("conference: {conference}"),
+                    format!("level: {}", entry.level),
+                    format!("place: {}", ordinal(entry.place)),
+                    format!("score: {}", entry.score),
+                ];
+                if let Some(advance) = &entry.advance {
+                    fields.push(format!(
+                        "advances: {}",
+                        match advance {
+                            AdvanceTypeIndividual::Indiv => "individual",
+                            AdvanceTypeIndividual::Team => "team",
+                            AdvanceTypeIndividual::Wild => "wildcard",
+                        }
+                    ));
+                }
+                println!("{}", fields.join("; "));
+            }
+            println!("{year}; reached: {reached}");
+            continue;
+        }
+
+        println!(
+            "  {year} {} (reached {reached}):",
+            theme.conference(year, entries[0].conference)
+        );
+        let subject_length = entries
+            .iter()
+            .map(|entry| entry.subject.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let level_length = entries
+            .iter()
+            .map(|entry| entry.level.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let score_length = entries
+            .iter()
+            .map(|entry| entry.score.to_string().len())
+            .max()
+            .unwrap_or(0);
+        for entry in entries {
+            let advance: ColoredString = match &entry.advance {
+                Some(advance) => {
+                    let (label, color) = theme.individual_badge(advance);
+                    label.color(color)
+                }
+                None => "".into(),
+            };
+            println!(
+                "    {:subject_length$}  {:level_length$}  {}  {:>score_length$}  {advance}",
+                entry.subject.to_string(),
+                entry.level.to_string(),
+                theme.medal(format!("{:>4}", ordinal(entry.place)), entry.place),
+                entry.score,
+            );
+        }
+    }
+    println!();
+}
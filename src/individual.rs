@@ -1,11 +1,17 @@
-use colored::{Color, ColoredString, Colorize};
+use colored::{ColoredString, Colorize};
 use scraper::{selectable::Selectable, *};
-use std::cmp::{self, Ordering};
-use supports_color::Stream;
+use std::cmp::Ordering;
+use std::sync::Arc;
 
 use crate::advance::AdvanceTypeIndividual;
 
-use crate::request::{RequestFields, Subject, district_as_region};
+use crate::output::{conference_places, percentile};
+use crate::request::{Level, RequestFields, Subject, district_as_region};
+use crate::results::Source;
+use crate::school;
+use crate::score::Score;
+use crate::sort::{SortKey, by_score, sort_placed};
+use crate::theme;
 
 #[derive(Clone, PartialEq, PartialOrd, Debug)]
 pub struct Individual {
@@ -14,40 +20,28 @@ pub struct Individual {
     pub conference: u8,
     pub district: Option<u8>,
     pub region: Option<u8>,
-    pub score: i16,
-    pub points: f32,
+    pub year: u16,
+    pub level: Level,
+    pub score: Score,
+    pub points: Score,
     pub advance: Option<AdvanceTypeIndividual>,
     pub misc: IndividualMisc,
-}
-
-impl Default for Individual {
-    fn default() -> Self {
-        Self {
-            name: String::new(),
-            school: String::new(),
-            district: None,
-            region: None,
-            conference: 0,
-            score: 0,
-            points: 0.0,
-            advance: None,
-            misc: IndividualMisc::Normal,
-        }
-    }
+    /// The page this row was scraped from
+    pub source: Option<Arc<Source>>,
 }
 
 #[derive(Clone, Eq, PartialEq, PartialOrd, Debug)]
 pub enum IndividualMisc {
     Normal,
     Science {
-        biology: i16,
-        chemistry: i16,
-        physics: i16,
+        biology: Score,
+        chemistry: Score,
+        physics: Score,
     },
 }
 
 impl Individual {
-    pub const fn get_biology(&self) -> Option<i16> {
+    pub const fn get_biology(&self) -> Option<Score> {
         match self.misc {
             IndividualMisc::Science {
                 biology,
@@ -58,7 +52,7 @@ impl Individual {
         }
     }
 
-    pub const fn get_chemistry(&self) -> Option<i16> {
+    pub const fn get_chemistry(&self) -> Option<Score> {
         match self.misc {
             IndividualMisc::Science {
                 biology: _,
@@ -68,7 +62,7 @@ impl Individual {
             _ => None,
         }
     }
-    pub const fn get_physics(&self) -> Option<i16> {
+    pub const fn get_physics(&self) -> Option<Score> {
         match self.misc {
             IndividualMisc::Science {
                 biology: _,
@@ -79,32 +73,14 @@ impl Individual {
         }
     }
 
-    pub fn get_ties(sorted: Vec<Self>) -> Vec<Vec<Self>> {
-        let mut groups: Vec<Vec<Self>> = Vec::new();
-        let mut current_group: Vec<Self> = Vec::new();
-
-        for (i, individual) in sorted.iter().enumerate() {
-            if i == 0 || individual.score == sorted[i - 1].score {
-                current_group.push(individual.clone());
-            } else {
-                groups.push(current_group);
-                current_group = vec![individual.clone()];
-            }
-        }
-
-        if !current_group.is_empty() {
-            groups.push(current_group);
-        }
-
-        groups
-    }
-
     pub fn parse_table(table: ElementRef, fields: &RequestFields) -> Option<Vec<Self>> {
         let mut results: Vec<Self> = Vec::new();
 
         let row_selector = Selector::parse("tr").ok()?;
         let cell_selector = Selector::parse("td").ok()?;
 
+        let level = fields.level()?;
+
         let mut place_index = 0;
         let mut points_index = 0;
         let mut advance_index = 0;
@@ -146,11 +122,10 @@ impl Individual {
             };
             let mut school = cells[school_index].trim().to_string();
             if fields.year <= 2022 {
+                // Legacy postings add the city after the school
                 let _ = school.split_off(school.find(", ").unwrap_or(school.len()));
-                if school.split_off(school.find("H S").unwrap_or(school.len())) == "H S" {
-                    school += "HS";
-                }
             }
+            let school = school::normalize(&school);
 
             let conference = fields.conference;
             let district = fields.district;
@@ -161,11 +136,10 @@ impl Individual {
                 Subject::SocialStudies => &cells[if fields.year > 2022 { 6 } else { score_index }],
                 _ => &cells[score_index],
             }
-            .trim()
-            .parse::<f32>()
-            .unwrap_or(0.0) as i16;
+            .trim();
+            let score = Score::parse(score).unwrap_or_default();
 
-            let points = cells[points_index].parse::<f32>().unwrap_or(0.0);
+            let points = Score::parse(&cells[points_index]).unwrap_or_default();
 
             let advance_str = &cells[advance_index];
             let advance = match advance_str.as_str() {
@@ -176,9 +150,9 @@ impl Individual {
             let misc: IndividualMisc = if fields.year > 2022 {
                 match fields.clone().subject {
                     Subject::Science => IndividualMisc::Science {
-                        biology: cells[4].parse::<f32>().unwrap_or(0.0) as i16,
-                        chemistry: cells[5].parse::<f32>().unwrap_or(0.0) as i16,
-                        physics: cells[6].parse::<f32>().unwrap_or(0.0) as i16,
+                        biology: Score::parse(&cells[4]).unwrap_or_default(),
+                        chemistry: Score::parse(&cells[5]).unwrap_or_default(),
+                        physics: Score::parse(&cells[6]).unwrap_or_default(),
                     },
                     _ => IndividualMisc::Normal {},
                 }
@@ -192,10 +166,13 @@ impl Individual {
                 conference,
                 district,
                 region,
+                year: fields.year,
+                level,
                 score,
                 points,
                 advance,
                 misc,
+                source: None,
             };
             results.push(individual);
         }
@@ -205,28 +182,25 @@ impl Individual {
             copy.sort_by_key(|a| std::cmp::Reverse(a.get_biology()));
             let top_bio = copy
                 .first()
-                .unwrap_or(&Individual::default())
-                .get_biology()
-                .unwrap_or(0);
+                .and_then(Individual::get_biology)
+                .unwrap_or_default();
 
             copy.sort_by_key(|a| std::cmp::Reverse(a.get_chemistry()));
             let top_chem = copy
                 .first()
-                .unwrap_or(&Individual::default())
-                .get_chemistry()
-                .unwrap_or(0);
+                .and_then(Individual::get_chemistry)
+                .unwrap_or_default();
 
             copy.sort_by_key(|a| std::cmp::Reverse(a.get_physics()));
             let top_phys = copy
                 .first()
-                .unwrap_or(&Individual::default())
-                .get_physics()
-                .unwrap_or(0);
+                .and_then(Individual::get_physics)
+                .unwrap_or_default();
 
             for result in results.iter_mut() {
-                if result.get_biology().unwrap_or(-120) == top_bio
-                    || result.get_chemistry().unwrap_or(-120) == top_chem
-                    || result.get_physics().unwrap_or(-120) == top_phys
+                if result.get_biology() == Some(top_bio)
+                    || result.get_chemistry() == Some(top_chem)
+                    || result.get_physics() == Some(top_phys)
                 {
                     result.advance = Some(AdvanceTypeIndividual::Indiv);
                 }
@@ -236,9 +210,8 @@ impl Individual {
         Some(results)
     }
 
-    pub fn display_results(mut results: Vec<Self>, positions: usize, find: &Option<String>) {
-        let support = supports_color::on(Stream::Stdout);
-
+    /// Sorts results by score, then conference, then school, and drops duplicates
+    pub fn sort_results(results: &mut Vec<Self>) {
         results.sort_by(|a, b| {
             let a_score = a.score;
             let b_score = b.score;
@@ -254,6 +227,35 @@ impl Individual {
         });
 
         results.dedup();
+    }
+
+    /// The place of each sorted result, starting at 1, with tied scores sharing a place
+    pub fn places(sorted: &[Self]) -> Vec<usize> {
+        let mut places = Vec::new();
+        for (index, individual) in sorted.iter().enumerate() {
+            if index > 0 && individual.score == sorted[index - 1].score {
+                places.push(places[index - 1]);
+            } else {
+                places.push(index + 1);
+            }
+        }
+        places
+    }
+
+    pub fn matches(&self, find: &str) -> bool {
+        self.name.contains(find) || self.school.contains(find)
+    }
+
+    pub fn display_results(
+        mut results: Vec<Self>,
+        positions: usize,
+        find: &Option<String>,
+        sort: &[SortKey],
+        cutoff: bool,
+    ) {
+        let theme = theme::theme();
+
+        Self::sort_results(&mut results);
 
         let mut longest_individual_name = 0;
 
@@ -277,156 +279,114 @@ impl Individual {
             longest_individual_name = individual.name.len();
         }
 
-        let place_length = if find.is_none() {
-            results.len().checked_ilog10().unwrap_or(0) as usize + 1
+        // Results from different conferences also get their place within their own conference
+        let mixed = results.iter().any(|individual| {
+            Some(individual.conference) != results.first().map(|first| first.conference)
+        });
+        let count = results.len();
+        let places = Self::places(&results);
+        let conference_places = conference_places(&results, |individual| {
+            (individual.conference, individual.score)
+        });
+        let mut placed: Vec<((usize, usize), Self)> = places
+            .into_iter()
+            .zip(conference_places)
+            .zip(results)
+            .collect();
+        sort_placed(&mut placed, sort, Self::compare);
+
+        let place_length = placed
+            .iter()
+            .filter(|(_, individual)| find.as_ref().is_none_or(|find| individual.matches(find)))
+            .map(|((place, _), _)| place.checked_ilog10().unwrap_or(0) as usize + 1)
+            .max()
+            .unwrap_or(1);
+        let conference_place_length = placed
+            .iter()
+            .map(|((_, place), _)| place.checked_ilog10().unwrap_or(0) as usize + 1)
+            .max()
+            .unwrap_or(1);
+
+        let score_length = placed
+            .iter()
+            .map(|(_, individual)| individual.score.to_string().len())
+            .max()
+            .unwrap_or(1);
+
+        // The line under the last advancing row, when each meet is shown on its own.
+        // It's found before rows are left out, and only means anything in score order.
+        let last_advancing = if cutoff && by_score(sort) {
+            placed
+                .iter()
+                .rposition(|(_, individual)| individual.advance.is_some())
         } else {
-            let mut previous_score = results.first().unwrap().score;
-            let mut previous_place = 0;
-            let mut longest_place = 0;
-            for (place, individual) in results.iter().enumerate() {
-                let name = individual.name.clone();
-                let school = individual.school.clone();
-                let score = individual.score;
-
-                let place = if score == previous_score {
-                    previous_place
-                } else {
-                    place
-                };
-
-                if score != previous_score {
-                    previous_score = score;
-                }
-                previous_place = place;
-
-                if name.contains(&find.clone().unwrap()) || school.contains(&find.clone().unwrap())
-                {
-                    longest_place = place;
-                }
-            }
-            longest_place.checked_ilog10().unwrap_or(0) as usize + 1
+            None
         };
+        let cutoff_line = "-".repeat(place_length + longest_individual_name + score_length + 5);
+        let mut shown_above = false;
+        let mut cut = false;
 
-        let score_length =
-            results.first().unwrap().score.checked_ilog10().unwrap_or(0) as usize + 1;
-
-        let mut previous_score = results.first().unwrap().score;
-        let mut previous_place = 0;
-        for (place, individual) in results.iter().enumerate() {
+        for (index, ((place, conference_place), individual)) in placed.iter().enumerate() {
+            // Places start at 1, but the rest of the layout counts from 0
+            let place = place - 1;
             let name = individual.name.clone();
             let school = individual.school.clone();
             let conference = individual.conference;
             let score = individual.score;
             let advance = &individual.advance;
 
-            let place = if score == previous_score {
-                previous_place
-            } else {
-                place
-            };
-
-            if score != previous_score {
-                previous_score = score;
+            if positions != 0 && find.is_none() && place >= positions {
+                continue;
             }
-            previous_place = place;
 
-            if positions != 0 && find.is_none() && place >= cmp::min(results.len(), positions) {
-                break;
+            if let Some(find_name) = find.clone()
+                && !name.contains(&find_name)
+                && !school.contains(&find_name)
+            {
+                continue;
             }
 
-            if let Some(find_name) = find.clone() {
-                if !name.contains(&find_name) && !school.contains(&find_name) {
-                    continue;
+            if let Some(last_advancing) = last_advancing {
+                if index > last_advancing && shown_above && !cut {
+                    println!("{cutoff_line}");
+                    cut = true;
                 }
+                shown_above |= index <= last_advancing;
             }
 
-            let mut base: ColoredString = format!(
-                "{:place_length$} {:longest_individual_name$} => {:>score_length$}",
+            let mut base = theme.medal(
+                format!(
+                    "{:place_length$} {:longest_individual_name$} => {:>score_length$}",
+                    place + 1,
+                    name,
+                    score
+                ),
                 place + 1,
-                name,
-                score
-            )
-            .into();
-            match place + 1 {
-                1 => {
-                    base.fgcolor = Some(Color::Black);
-                    base.bgcolor = Some(Color::Yellow);
-                }
-                2 => {
-                    base.fgcolor = Some(Color::Black);
-                    base.bgcolor = Some(Color::BrightWhite);
-                }
-                3 => {
-                    base.fgcolor = Some(Color::Black);
-                    base.bgcolor = Some(Color::BrightRed);
-                }
-
-                _ => base.fgcolor = None,
-            };
+            );
+
+            let mut conference_str = theme.conference(individual.year, conference);
+            if mixed {
+                base.input = format!("{} {:>5.1}%", base.input, percentile(place + 1, count));
+                conference_str.input = format!(
+                    "{} #{conference_place:<conference_place_length$}",
+                    conference_str.input
+                );
+            }
 
-            let mut conference_str: ColoredString = match conference {
-                1 => "1A".white(),
-                2 => "2A".yellow(),
-                3 => "3A".bright_blue(),
-                4 => "4A".green(),
-                5 => "5A".red(),
-                6 => "6A".magenta(),
-                _ => "".into(),
+            let advance_str: ColoredString = match advance {
+                Some(advance) => {
+                    let (label, color) = theme.individual_badge(advance);
+                    label.color(color)
+                }
+                None => "    ".into(),
             };
 
-            let mut advance_str: ColoredString = match advance {
-                Some(AdvanceTypeIndividual::Indiv) => "Indv".green(),
-                Some(AdvanceTypeIndividual::Team) => "Team".blue(),
-                Some(AdvanceTypeIndividual::Wild) => "Wild".truecolor(0xFF, 0xA5, 0x00),
-                None => "    ".red(),
-            };
+            if let Some(district) = individual.district {
+                let region =
+                    district_as_region(Some(district), individual.year, conference).unwrap_or(0);
 
-            match support {
-                Some(support) => {
-                    if !support.has_basic {
-                        base.fgcolor = None;
-                        base.bgcolor = None;
-                        advance_str.fgcolor = None;
-                        advance_str.bgcolor = None;
-                        conference_str.fgcolor = None;
-                        conference_str.bgcolor = None;
-                    }
-                }
-                _ => {
-                    base.fgcolor = None;
-                    base.bgcolor = None;
-                    advance_str.fgcolor = None;
-                    advance_str.bgcolor = None;
-                    conference_str.fgcolor = None;
-                    conference_str.bgcolor = None;
-                }
-            };
+                let region_str = theme.region(format!("R{region}"), region);
 
-            let district = individual.district;
-            if district.is_some() {
-                let region = district_as_region(district).unwrap_or(0);
-
-                let mut region_str: ColoredString = match region {
-                    1 => "R1".red(),
-                    2 => "R2".yellow(),
-                    3 => "R3".green(),
-                    4 => "R4".blue(),
-                    _ => "".into(),
-                };
-                match support {
-                    Some(support) => {
-                        if !support.has_basic {
-                            region_str.fgcolor = None;
-                            region_str.bgcolor = None;
-                        }
-                    }
-                    _ => {
-                        region_str.fgcolor = None;
-                        region_str.bgcolor = None;
-                    }
-                };
-
-                let district = district.unwrap();
                 println!(
                     "{base} ({conference_str} D{district:<2} {region_str} - {advance_str} - {school})"
                 );
@@ -436,5 +396,8 @@ impl Individual {
                 println!("{base} ({conference_str} - {school})");
             }
         }
+        if shown_above && !cut {
+            println!("{cutoff_line}");
+        }
     }
 }
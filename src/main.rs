@@ -10,6 +10,8 @@ use request::*;
 
 mod advance;
 
+mod alignment;
+
 mod individual;
 use individual::*;
 
@@ -19,18 +21,103 @@ use team::*;
 mod cli;
 use cli::*;
 
+mod conference;
+
+mod competitor;
+use competitor::{CompetitorKey, Matching, normalize_name, normalize_school};
+
+mod results;
+
+mod score;
+use results::ResultSet;
+
+mod school;
+
+mod theme;
+
+mod tui;
+
+mod sort;
+
 mod scrape;
 use scrape::scrape_subject;
 
 mod overall;
 
+mod cutoffs;
+
+mod stats;
+
+mod strength;
+
+mod projection;
+
+mod history;
+
+mod profile;
+
+mod sweepstakes;
+
+mod trend;
+
+mod output;
+use output::{Format, Report, ReportKind};
+
 use clap::Parser;
 
 fn main() {
     let start = Instant::now();
     let mut cli = Cli::parse();
 
-    let subject = Subject::from_str(&cli.subject).unwrap();
+    let color = if cli.plain {
+        theme::ColorChoice::Never
+    } else {
+        cli.color
+    };
+    if let Err(err) = theme::load(cli.theme.as_deref(), color) {
+        eprintln!("{}", format!("Couldn't load the theme: {err}").red());
+        return;
+    }
+    if let Err(err) = school::load(cli.aliases.as_deref()) {
+        eprintln!("{}", format!("Couldn't load school aliases: {err}").red());
+        return;
+    }
+    if let Err(err) = alignment::load(cli.alignment.as_deref()) {
+        eprintln!(
+            "{}",
+            format!("Couldn't load the alignment table: {err}").red()
+        );
+        return;
+    }
+
+    if let Some(Commands::History {
+        name,
+        school,
+        years,
+        conferences,
+    }) = cli.command.clone()
+    {
+        history::run(&name, school.as_deref(), years, conferences, &cli);
+        return;
+    }
+
+    if let Some(Commands::School {
+        name,
+        year,
+        conferences,
+    }) = cli.command.clone()
+    {
+        profile::run(&name, year, conferences, &cli);
+        return;
+    }
+
+    let Some(subject) = cli.subject.as_deref().and_then(Subject::from_str) else {
+        match cli.subject.as_deref() {
+            Some(subject) => println!("{}", format!("Unknown subject {subject}").red()),
+            None => println!("{}", "You must specify a subject".red()),
+        }
+        return;
+    };
     let year = cli
         .year
         .unwrap_or(chrono::Utc::now().year().try_into().unwrap());
@@ -39,8 +126,49 @@ fn main() {
         find_level(&mut cli);
     }
 
-    let conferences =
-        RequestFields::parse_range(cli.conference.clone().unwrap_or(String::from("16"))).unwrap();
+    let matching = cli.matching();
+
+    let mut conferences = match cli.conference.clone() {
+        Some(conference) => match RequestFields::parse_range(conference.clone()) {
+            Some(conferences) => conferences,
+            None => {
+                eprintln!("{}", format!("Unknown conference {conference}").red());
+                return;
+            }
+        },
+        None => conference::all_codes(),
+    };
+
+    if let Some(Commands::Trend {
+        school,
+        years,
+        district,
+        region,
+        state,
+    }) = cli.command.clone()
+    {
+        if !district && !region && !state {
+            println!(
+                "{}",
+                "You must specify the level using --district, --region, or --state".red()
+            );
+            return;
+        }
+        let fields = RequestFields {
+            district: district.then_some(0),
+            region: region.then_some(0),
+            state,
+            subject: subject.clone(),
+            conference: 0,
+            year,
+        };
+        trend::run(subject, school.as_deref(), years, fields, conferences, &cli);
+        return;
+    }
+
+    if !cli.highscores && !check_conferences(year, &mut conferences, cli.conference.is_some()) {
+        return;
+    }
 
     if cli.command.is_none() {
         #[allow(unused_variables)]
@@ -53,7 +181,23 @@ fn main() {
             year,
         };
         if cli.highscores {
-            overall::highscores(fields, conferences.clone(), cli);
+            let results = overall::highscores(fields, conferences.clone(), &cli);
+            let report = Report::new(ReportKind::Highscores, subject, results);
+            output::emit(&report, &cli);
+            return;
+        }
+    }
+
+    if cli.project {
+        if matches!(subject, Subject::Rankings | Subject::Sweepstakes) {
+            eprintln!("{}", "Projections only work for a single subject".red());
+            return;
+        }
+        if cli.state || (cli.district.is_none() && cli.region.is_none()) {
+            eprintln!(
+                "{}",
+                "Projections need district or region results to project from".red()
+            );
             return;
         }
     }
@@ -67,11 +211,7 @@ fn main() {
             conference: 0,
             year,
         };
-        match subject {
-            Subject::Rankings => overall::rankings(fields, conferences.clone(), cli.mute),
-            Subject::Sweepstakes => overall::sweepstakes(fields, conferences.clone(), cli.mute),
-            _ => scrape_subject(fields, conferences.clone(), cli.mute),
-        }
+        scrape_results(fields, conferences.clone(), &cli)
     } else if let Some(Commands::Compare {
         person_a: _,
         person_b: _,
@@ -81,8 +221,11 @@ fn main() {
         state,
     }) = cli.command.clone()
     {
-        let conferences = RequestFields::parse_range(conferences)
+        let mut conferences = RequestFields::parse_range(conferences)
             .expect("Conferences entered in the wrong order");
+        if !check_conferences(year, &mut conferences, true) {
+            return;
+        }
 
         let district = if district { Some(0) } else { None };
         let region = if region { Some(0) } else { None };
@@ -96,40 +239,162 @@ fn main() {
             year,
         };
 
-        let (individual_results, team_results) = match subject {
-            Subject::Rankings => overall::rankings(fields, conferences.clone(), cli.mute),
-            Subject::Sweepstakes => overall::sweepstakes(fields, conferences.clone(), cli.mute),
-            _ => scrape_subject(fields.clone(), conferences.clone(), cli.mute),
-        }
-        .expect("No results found");
+        let results = scrape_results(fields, conferences.clone(), &cli).expect("No results found");
 
-        if individual_results.is_empty() || team_results.is_empty() {
+        if results.individuals.is_empty() || results.teams.is_empty() {
             None
         } else {
-            Some((individual_results, team_results))
+            Some(results)
         }
     } else {
         None
     };
-    if results.is_none() {
-        println!("{}", "Didn't return any results".red());
+    let Some(ResultSet {
+        individuals: mut individual_results,
+        teams: mut team_results,
+        sources,
+        events,
+    }) = results
+    else {
+        eprintln!("{}", "Didn't return any results".red());
         return;
+    };
+
+    if let Some(Commands::Compare {
+        person_a,
+        person_b,
+        conferences: _,
+        district: _,
+        region: _,
+        state: _,
+    }) = cli.command.clone()
+    {
+        let names = [
+            normalize_name(&person_a, matching.ignore_middle_names),
+            normalize_name(&person_b, matching.ignore_middle_names),
+        ];
+        let schools = [normalize_school(&person_a), normalize_school(&person_b)];
+        individual_results
+            .retain(|x| names.contains(&normalize_name(&x.name, matching.ignore_middle_names)));
+        team_results.retain(|x| schools.contains(&normalize_school(&x.school)));
     }
 
-    let (mut individual_results, mut team_results) = results.unwrap();
-
-    if cli.command.is_some() {
-        let Commands::Compare {
-            person_a,
-            person_b,
-            conferences: _,
-            district: _,
-            region: _,
-            state: _,
-        } = cli.command.clone().unwrap();
-        individual_results.retain(|x| x.name == person_a || x.name == person_b);
-        team_results.retain(|x| x.school == person_a || x.school == person_b);
+    let report = finish_results(
+        subject.clone(),
+        ResultSet {
+            individuals: individual_results,
+            teams: team_results,
+            sources,
+            events,
+        },
+        matching,
+    );
+    // Projections start from who advanced, including as part of a team
+    let report = if cli.project {
+        let projected = projection::project(ResultSet {
+            individuals: report.individuals,
+            teams: report.teams,
+            sources: report.sources,
+            events: Vec::new(),
+        });
+        finish_results(subject.clone(), projected, matching)
+    } else {
+        report
+    };
+    if cli.project {
+        if cli.format == Format::Text {
+            println!("{}", projection::CAVEAT.yellow());
+            println!();
+        } else {
+            eprintln!("{}", projection::CAVEAT.yellow());
+        }
     }
+    if cli.cutoffs {
+        cutoffs::print(&report, &cli);
+        return;
+    }
+    if cli.stats {
+        stats::print(&report, &cli);
+        return;
+    }
+    if cli.strength {
+        strength::print(&report, &cli);
+        return;
+    }
+    if cli.tui {
+        let fields = RequestFields {
+            district: cli.district,
+            region: cli.region,
+            state: cli.state,
+            subject: subject.clone(),
+            conference: 0,
+            year,
+        };
+        let compare = cli.command.is_some();
+        let result = tui::run(report, fields.level(), cli.sort.clone(), |level| {
+            if compare {
+                return None;
+            }
+            let fields = tui::fields_for(&fields, level);
+            let results = scrape_results(fields, conferences.clone(), &cli)?;
+            Some(finish_results(subject.clone(), results, matching))
+        });
+        if let Err(err) = result {
+            eprintln!("{}", format!("Couldn't run the terminal UI: {err}").red());
+        }
+        return;
+    }
+    output::emit(&report, &cli);
+
+    if cli.format == Format::Text {
+        println!("Time elapsed: {:?}", start.elapsed());
+    }
+}
+
+/// Scrapes one set of results, combining them for rankings and sweepstakes
+fn scrape_results(fields: RequestFields, conferences: Vec<u8>, cli: &Cli) -> Option<ResultSet> {
+    let matching = cli.matching();
+    match fields.subject {
+        Subject::Rankings => overall::rankings(fields, conferences, cli.mute, matching),
+        Subject::Sweepstakes => overall::sweepstakes(fields, conferences, cli.mute, matching),
+        _ => scrape_subject(fields, conferences, cli.mute),
+    }
+}
+
+/// Scrapes one level of a subject (0 for every district or region) and works out who advanced
+fn scrape_meet(
+    subject: &Subject,
+    year: u16,
+    level: Level,
+    conferences: Vec<u8>,
+    cli: &Cli,
+) -> Option<Report> {
+    let fields = RequestFields {
+        district: match level {
+            Level::District(district) => Some(district),
+            _ => None,
+        },
+        region: match level {
+            Level::Region(region) => Some(region),
+            _ => None,
+        },
+        state: level == Level::State,
+        subject: subject.clone(),
+        conference: 0,
+        year,
+    };
+    let results = scrape_subject(fields, conferences, cli.mute)?;
+    Some(finish_results(subject.clone(), results, cli.matching()))
+}
+
+/// Works out who advanced
+fn finish_results(subject: Subject, results: ResultSet, matching: Matching) -> Report {
+    let ResultSet {
+        individuals: mut individual_results,
+        teams: mut team_results,
+        sources,
+        events,
+    } = results;
 
     if !team_results.is_empty() && !individual_results.is_empty() {
         let advancing_teams = Team::get_advancing(team_results.clone());
@@ -142,9 +407,12 @@ fn main() {
         let mut advancing_individuals = HashMap::new();
         for indiv in individual_results.iter_mut() {
             let advance = indiv.advance.clone();
-            let team = team_results
-                .iter()
-                .find(|&team| team.school == indiv.school.clone());
+            let key = CompetitorKey::team_of(indiv, matching);
+            let team = team_results.iter().find(|&team| {
+                CompetitorKey::team(team, matching) == key
+                    && team.year == indiv.year
+                    && team.level == indiv.level
+            });
 
             if team.is_none() {
                 continue;
@@ -157,15 +425,11 @@ fn main() {
             }
             let team_advance = team_advance.clone().unwrap();
 
-            if let Some(count) = advancing_individuals.get(&team.school) {
-                if *count >= 4 {
-                    continue;
-                }
-
-                advancing_individuals.insert(team.school.clone(), *count + 1);
-            } else {
-                advancing_individuals.insert(team.school.clone(), 1);
+            let count = advancing_individuals.entry(key).or_insert(0);
+            if *count >= 4 {
+                continue;
             }
+            *count += 1;
 
             if advance.is_some() {
                 continue;
@@ -179,92 +443,61 @@ fn main() {
         }
     }
 
-    if !individual_results.is_empty() {
-        if subject == Subject::Sweepstakes {
-            individual_results = individual_results
-                .iter()
-                .map(|individual| {
-                    let mut copy = individual.clone();
-                    copy.score = copy.points.round() as i16;
-                    copy
-                })
-                .collect();
-        }
-        println!("Individual Total Scores:");
-        Individual::display_results(
-            individual_results.clone(),
-            cli.individual_positions.unwrap_or(25),
-            &cli.find,
-        );
-        println!();
-        if subject == Subject::Science {
-            let mut biology = individual_results.clone();
-            biology.retain_mut(|x| {
-                x.score = x.get_biology().unwrap_or(0);
-                true
-            });
-            let mut chemistry = individual_results.clone();
-            chemistry.retain_mut(|x| {
-                x.score = x.get_chemistry().unwrap_or(0);
-                true
-            });
-            let mut physics = individual_results.clone();
-            physics.retain_mut(|x| {
-                x.score = x.get_physics().unwrap_or(0);
-                true
-            });
-            println!("Individual Biology Scores:");
-            Individual::display_results(biology, cli.individual_positions.unwrap_or(25), &cli.find);
-            println!();
-            println!("Individual Chemistry Scores:");
-            Individual::display_results(
-                chemistry,
-                cli.individual_positions.unwrap_or(25),
-                &cli.find,
+    let mut report = Report::new(
+        ReportKind::Results,
+        subject,
+        ResultSet {
+            individuals: individual_results,
+            teams: team_results,
+            sources,
+            events: Vec::new(),
+        },
+    );
+    report.events = events
+        .into_iter()
+        .map(|(subject, results)| finish_results(subject, results, matching))
+        .collect();
+    report
+}
+
+/// Drops the conferences that didn't exist in `year`, saying so if they were asked for.
+/// Returns false if there's nothing left to look for.
+fn check_conferences(year: u16, conferences: &mut Vec<u8>, requested: bool) -> bool {
+    let missing = conference::retain_existing(year, conferences);
+    if requested {
+        for code in missing {
+            eprintln!(
+                "{}",
+                format!(
+                    "{} didn't exist in {year}, skipping it",
+                    conference::label(year, code)
+                )
+                .red()
             );
-            println!();
-            println!("Individual Physics Scores:");
-            Individual::display_results(physics, cli.individual_positions.unwrap_or(25), &cli.find);
-            println!();
         }
     }
-    if !team_results.is_empty() {
-        if subject == Subject::Sweepstakes {
-            team_results = team_results
-                .iter()
-                .map(|team| {
-                    let mut copy = team.clone();
-                    for indiv in &individual_results {
-                        if indiv.school == copy.school {
-                            copy.points += indiv.points;
-                        }
-                    }
-                    copy.score = copy.points.round() as i16;
-                    copy.misc = TeamMisc::Normal;
-                    copy
-                })
-                .collect();
-        }
-        println!("Team Scores:");
-        Team::display_results(
-            team_results,
-            subject,
-            cli.team_positions.unwrap_or(25),
-            &cli.find,
+    if conferences.is_empty() {
+        eprintln!(
+            "{}",
+            format!("No matching conferences existed in {year}").red()
         );
+        return false;
     }
-
-    println!("Time elapsed: {:?}", start.elapsed());
+    true
 }
 
 pub fn find_level(cli: &mut Cli) {
-    let subject = Subject::from_str(&cli.subject).unwrap_or(Subject::Mathematics);
+    let subject = cli
+        .subject
+        .as_deref()
+        .and_then(Subject::from_str)
+        .unwrap_or(Subject::Mathematics);
     let year = cli
         .year
         .unwrap_or(chrono::Utc::now().year().try_into().unwrap_or(2004));
 
     while cli.district.is_none() && cli.region.is_none() && !cli.state {
-        println!(
+        eprintln!(
             "{}",
             "You must specify the level using --district, --region, or --state".red()
         );
@@ -280,7 +513,7 @@ pub fn find_level(cli: &mut Cli) {
 
         if request.is_some() {
             cli.state = true;
-            println!("Defaulting to state");
+            eprintln!("Defaulting to state");
             break;
         }
 
@@ -295,7 +528,7 @@ pub fn find_level(cli: &mut Cli) {
 
         if request.is_some() {
             cli.region = Some(0);
-            println!("Defaulting to region");
+            eprintln!("Defaulting to region");
             break;
         }
 
@@ -310,7 +543,7 @@ pub fn find_level(cli: &mut Cli) {
 
         if request.is_some() {
             cli.district = Some(0);
-            println!("Defaulting to district");
+            eprintln!("Defaulting to district");
             break;
         }
     }
@@ -0,0 +1,902 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use colored::{Color, Colorize};
+use serde::Serialize;
+
+use crate::{
+    advance::{AdvanceTypeIndividual, AdvanceTypeTeam},
+    cli::Cli,
+    conference,
+    individual::Individual,
+    request::{Subject, district_as_region},
+    results::{ResultSet, Source},
+    score::Score,
+    sort::{SortKey, sort_placed},
+    team::{Team, TeamMisc},
+    theme::theme,
+};
+
+mod html;
+mod json;
+mod markdown;
+mod spreadsheet;
+mod tables;
+mod text;
+
+pub use spreadsheet::file_stem;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Colored tables in the terminal
+    #[default]
+    Text,
+    /// One JSON document with every table and where the results came from
+    Json,
+    /// One JSON record per line, for streaming into tools like jq
+    Jsonl,
+    /// CSV files for individuals, teams and subject parts, written to `--output`
+    Csv,
+    /// Markdown tables, for posting to a team site or group chat
+    Markdown,
+    /// A self-contained HTML page with sortable columns
+    Html,
+    /// An Excel workbook with a summary sheet, written to `--output`
+    Xlsx,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ReportKind {
+    /// Results from one year, ranked against each other
+    Results,
+    /// The best results across many years
+    Highscores,
+}
+
+/// Highlighting for the top three places
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Medal {
+    Gold,
+    Silver,
+    Bronze,
+}
+
+impl Medal {
+    pub const fn from_place(place: usize) -> Option<Self> {
+        match place {
+            1 => Some(Self::Gold),
+            2 => Some(Self::Silver),
+            3 => Some(Self::Bronze),
+            _ => None,
+        }
+    }
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::Gold => "gold",
+            Self::Silver => "silver",
+            Self::Bronze => "bronze",
+        }
+    }
+}
+
+/// A part of a subject that is also scored on its own
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Part {
+    Biology,
+    Chemistry,
+    Physics,
+}
+
+impl Part {
+    pub const fn name(&self) -> &str {
+        match self {
+            Self::Biology => "Biology",
+            Self::Chemistry => "Chemistry",
+            Self::Physics => "Physics",
+        }
+    }
+
+    pub const fn key(&self) -> &'static str {
+        match self {
+            Self::Biology => "biology",
+            Self::Chemistry => "chemistry",
+            Self::Physics => "physics",
+        }
+    }
+
+    pub const fn score(&self, individual: &Individual) -> Option<Score> {
+        match self {
+            Self::Biology => individual.get_biology(),
+            Self::Chemistry => individual.get_chemistry(),
+            Self::Physics => individual.get_physics(),
+        }
+    }
+}
+
+/// A field that can be picked with `--columns`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Column {
+    /// Place among everyone shown together
+    Place,
+    /// Place among competitors from the same conference
+    ConferencePlace,
+    /// Share of the field placed at or below the competitor
+    Percentile,
+    /// Competitor name (left out of team tables)
+    Name,
+    School,
+    Conference,
+    District,
+    Region,
+    Year,
+    Level,
+    Score,
+    Points,
+    Advance,
+    Biology,
+    Chemistry,
+    Physics,
+    /// Computer Science programming score (only in team tables)
+    Prog,
+    /// Sweepstakes points by event (only in team tables)
+    Events,
+    Source,
+}
+
+impl Column {
+    /// The header used in tables
+    pub const fn header(&self) -> &'static str {
+        match self {
+            Self::Place => "Place",
+            Self::ConferencePlace => "Conf Place",
+            Self::Percentile => "Percentile",
+            Self::Name => "Name",
+            Self::School => "School",
+            Self::Conference => "Conference",
+            Self::District => "District",
+            Self::Region => "Region",
+            Self::Year => "Year",
+            Self::Level => "Level",
+            Self::Score => "Score",
+            Self::Points => "Points",
+            Self::Advance => "Advance",
+            Self::Biology => "Biology",
+            Self::Chemistry => "Chemistry",
+            Self::Physics => "Physics",
+            Self::Prog => "Prog",
+            Self::Events => "Events",
+            Self::Source => "Source",
+        }
+    }
+
+    /// The key used in CSV headers and JSON records
+    pub const fn key(&self) -> &'static str {
+        match self {
+            Self::Place => "place",
+            Self::ConferencePlace => "conference_place",
+            Self::Percentile => "percentile",
+            Self::Name => "name",
+            Self::School => "school",
+            Self::Conference => "conference",
+            Self::District => "district",
+            Self::Region => "region",
+            Self::Year => "year",
+            Self::Level => "level",
+            Self::Score => "score",
+            Self::Points => "points",
+            Self::Advance => "advance",
+            Self::Biology => "biology",
+            Self::Chemistry => "chemistry",
+            Self::Physics => "physics",
+            Self::Prog => "prog",
+            Self::Events => "events",
+            Self::Source => "source",
+        }
+    }
+
+    /// Whether the column is a number, and so right aligned
+    pub const fn is_numeric(&self) -> bool {
+        matches!(
+            self,
+            Self::Place
+                | Self::ConferencePlace
+                | Self::Percentile
+                | Self::District
+                | Self::Region
+                | Self::Year
+                | Self::Score
+                | Self::Points
+                | Self::Biology
+                | Self::Chemistry
+                | Self::Physics
+                | Self::Prog
+        )
+    }
+
+    const fn for_individuals(&self) -> bool {
+        !matches!(self, Self::Prog | Self::Events)
+    }
+
+    const fn for_teams(&self) -> bool {
+        !matches!(
+            self,
+            Self::Name | Self::Biology | Self::Chemistry | Self::Physics
+        )
+    }
+}
+
+/// The columns asked for with `--columns` that individual tables have, or `defaults`
+pub fn individual_columns(requested: &[Column], defaults: Vec<Column>) -> Vec<Column> {
+    if requested.is_empty() {
+        return defaults;
+    }
+    requested
+        .iter()
+        .copied()
+        .filter(Column::for_individuals)
+        .collect()
+}
+
+/// The columns asked for with `--columns` that team tables have, or `defaults`
+pub fn team_columns(requested: &[Column], defaults: Vec<Column>) -> Vec<Column> {
+    if requested.is_empty() {
+        return defaults;
+    }
+    requested
+        .iter()
+        .copied()
+        .filter(Column::for_teams)
+        .collect()
+}
+
+/// The place of each sorted row among those from the same conference, given each
+/// row's conference and score
+pub fn conference_places<T>(sorted: &[T], key: impl Fn(&T) -> (u8, Score)) -> Vec<usize> {
+    // conference => (results seen, last score, last place)
+    let mut seen: HashMap<u8, (usize, Score, usize)> = HashMap::new();
+    sorted
+        .iter()
+        .map(|row| {
+            let (conference, score) = key(row);
+            let entry = seen.entry(conference).or_insert((0, Score::default(), 0));
+            entry.0 += 1;
+            if entry.0 == 1 || score != entry.1 {
+                entry.2 = entry.0;
+            }
+            entry.1 = score;
+            entry.2
+        })
+        .collect()
+}
+
+/// The share of `count` competitors placed at or below `place`, to one decimal
+pub fn percentile(place: usize, count: usize) -> f64 {
+    if count == 0 {
+        return 0.0;
+    }
+    let share = (count + 1 - place) as f64 / count as f64 * 100.0;
+    (share * 10.0).round() / 10.0
+}
+
+/// A place as it's read out, like "1st" or "22nd"
+pub fn ordinal(place: usize) -> String {
+    let suffix = match (place % 10, place % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{place}{suffix}")
+}
+
+/// A value as a table cell, blank when there isn't one
+pub fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// Prints a summary of the results (`what`, e.g. "Cutoffs") as JSON or JSON lines,
+/// or as text with `text`. Other formats are shown as text, saying so.
+pub fn emit_summary<T: Serialize>(
+    what: &str,
+    document: &impl Serialize,
+    lines: &[T],
+    cli: &Cli,
+    text: impl FnOnce(),
+) {
+    match cli.format {
+        Format::Json => match serde_json::to_string_pretty(document) {
+            Ok(json) => println!("{json}"),
+            Err(err) => eprintln!("{}", format!("Couldn't write JSON: {err}").red()),
+        },
+        Format::Jsonl => {
+            for line in lines {
+                match serde_json::to_string(line) {
+                    Ok(json) => println!("{json}"),
+                    Err(err) => eprintln!("{}", format!("Couldn't write JSON: {err}").red()),
+                }
+            }
+        }
+        Format::Text => text(),
+        format => {
+            eprintln!(
+                "{}",
+                format!(
+                    "{what} can't be shown as {}, showing them as text",
+                    format!("{format:?}").to_lowercase()
+                )
+                .red()
+            );
+            text();
+        }
+    }
+}
+
+/// How wide each column of an aligned table is: its header or its widest cell
+pub fn column_widths(headers: &[&str], rows: &[Vec<String>]) -> Vec<usize> {
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|header| header.chars().count())
+        .collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = std::cmp::max(*width, cell.chars().count());
+        }
+    }
+    widths
+}
+
+/// The bold header line of an aligned table
+pub fn print_header(headers: &[&str], widths: &[usize]) {
+    let header: Vec<String> = headers
+        .iter()
+        .zip(widths.iter())
+        .map(|(header, width)| format!("{header:width$}"))
+        .collect();
+    println!("{}", header.join("  ").trim_end().bold());
+}
+
+/// Prints rows under a bold header with every column lined up. The `left` column
+/// (usually a name) is left-aligned and the rest, which are numbers, right-aligned.
+pub fn print_aligned(headers: &[&str], rows: &[Vec<String>], left: usize) {
+    let widths = column_widths(headers, rows);
+    print_header(headers, &widths);
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths.iter())
+            .enumerate()
+            .map(|(index, (cell, width))| match index {
+                _ if index == left => format!("{cell:width$}"),
+                _ => format!("{cell:>width$}"),
+            })
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
+
+/// How `--group-by` splits results into separate meets
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    District,
+    Region,
+    Conference,
+}
+
+/// Which meet a row belongs to when grouping: its conference, then district or region
+fn group_key(
+    group_by: GroupBy,
+    conference: u8,
+    district: Option<u8>,
+    region: Option<u8>,
+    year: u16,
+) -> (u8, Option<u8>) {
+    let number = match group_by {
+        GroupBy::District => district,
+        GroupBy::Region => region.or(district_as_region(district, year, conference)),
+        GroupBy::Conference => None,
+    };
+    (conference, number)
+}
+
+/// Everything that gets shown or exported for one run
+pub struct Report {
+    pub kind: ReportKind,
+    pub subject: Subject,
+    pub individuals: Vec<Individual>,
+    pub teams: Vec<Team>,
+    /// Individual results ranked by one part of the subject (Science only)
+    pub parts: Vec<(Part, Vec<Individual>)>,
+    pub sources: Vec<Arc<Source>>,
+    /// Each event's own report, when rows were combined across events (rankings, sweepstakes)
+    pub events: Vec<Report>,
+    pub generated: DateTime<Utc>,
+}
+
+impl Report {
+    pub fn new(kind: ReportKind, subject: Subject, results: ResultSet) -> Self {
+        let mut parts = Vec::new();
+        if subject == Subject::Science {
+            for part in [Part::Biology, Part::Chemistry, Part::Physics] {
+                let mut individuals = results.individuals.clone();
+                individuals.retain_mut(|x| {
+                    x.score = part.score(x).unwrap_or_default();
+                    true
+                });
+                parts.push((part, individuals));
+            }
+        }
+
+        Self {
+            kind,
+            subject,
+            individuals: results.individuals,
+            teams: results.teams,
+            parts,
+            sources: results.sources,
+            events: results
+                .events
+                .into_iter()
+                .map(|(subject, results)| Self::new(kind, subject, results))
+                .collect(),
+            generated: Utc::now(),
+        }
+    }
+
+    /// Whether more than one conference is ranked together
+    pub fn mixes_conferences(&self) -> bool {
+        let mut conferences = self
+            .individuals
+            .iter()
+            .map(|individual| individual.conference)
+            .chain(self.teams.iter().map(|team| team.conference));
+        conferences
+            .next()
+            .is_some_and(|first| conferences.any(|conference| conference != first))
+    }
+
+    /// Splits the report into one report per meet, each with a label like "5A District 9"
+    pub fn groups(&self, group_by: GroupBy) -> Vec<(String, Self)> {
+        let individual_key = |individual: &Individual| {
+            group_key(
+                group_by,
+                individual.conference,
+                individual.district,
+                individual.region,
+                individual.year,
+            )
+        };
+        let team_key = |team: &Team| {
+            group_key(
+                group_by,
+                team.conference,
+                team.district,
+                team.region,
+                team.year,
+            )
+        };
+
+        let mut keys: Vec<(u8, Option<u8>)> = self
+            .individuals
+            .iter()
+            .map(individual_key)
+            .chain(self.teams.iter().map(team_key))
+            .collect();
+        keys.sort();
+        keys.dedup();
+
+        keys.into_iter()
+            .map(|key| {
+                let year = self
+                    .individuals
+                    .iter()
+                    .map(|individual| individual.year)
+                    .chain(self.teams.iter().map(|team| team.year))
+                    .next()
+                    .unwrap_or_default();
+                let conference = match key.0 {
+                    0 => String::new(),
+                    code => format!("{} ", conference::label(year, code)),
+                };
+                let label = match (group_by, key.1) {
+                    (GroupBy::District, Some(district)) => {
+                        format!("{conference}District {district}")
+                    }
+                    (GroupBy::Region, Some(region)) => format!("{conference}Region {region}"),
+                    _ => conference.trim_end().to_string(),
+                };
+
+                let report = Self {
+                    kind: self.kind,
+                    subject: self.subject.clone(),
+                    individuals: self
+                        .individuals
+                        .iter()
+                        .filter(|individual| individual_key(individual) == key)
+                        .cloned()
+                        .collect(),
+                    teams: self
+                        .teams
+                        .iter()
+                        .filter(|team| team_key(team) == key)
+                        .cloned()
+                        .collect(),
+                    parts: self
+                        .parts
+                        .iter()
+                        .map(|(part, individuals)| {
+                            let individuals = individuals
+                                .iter()
+                                .filter(|individual| individual_key(individual) == key)
+                                .cloned()
+                                .collect();
+                            (*part, individuals)
+                        })
+                        .collect(),
+                    sources: self.sources.clone(),
+                    events: Vec::new(),
+                    generated: self.generated,
+                };
+                (label, report)
+            })
+            .collect()
+    }
+
+    /// An individual's place among everyone at their meet
+    pub fn individual_place(&self, individual: &Individual) -> usize {
+        let mut meet: Vec<Individual> = self
+            .individuals
+            .iter()
+            .filter(|x| x.conference == individual.conference && x.level == individual.level)
+            .cloned()
+            .collect();
+        Individual::sort_results(&mut meet);
+        let places = Individual::places(&meet);
+        meet.iter()
+            .position(|x| x == individual)
+            .map(|index| places[index])
+            .unwrap_or_default()
+    }
+
+    /// A team's place among every team at its meet
+    pub fn team_place(&self, team: &Team) -> usize {
+        let mut meet: Vec<Team> = self
+            .teams
+            .iter()
+            .filter(|x| x.conference == team.conference && x.level == team.level)
+            .cloned()
+            .collect();
+        Team::sort_results(&mut meet);
+        let places = Team::places(&meet);
+        meet.iter()
+            .position(|x| x == team)
+            .map(|index| places[index])
+            .unwrap_or_default()
+    }
+
+    /// Individuals in the order they're shown, with their places
+    pub fn individual_records(
+        &self,
+        individuals: &[Individual],
+        find: &Option<String>,
+        sort: &[SortKey],
+    ) -> Vec<IndividualRecord> {
+        let mut individuals = individuals.to_vec();
+        match self.kind {
+            ReportKind::Results => Individual::sort_results(&mut individuals),
+            ReportKind::Highscores => {
+                individuals.sort_by(|a, b| b.score.cmp(&a.score).then(a.year.cmp(&b.year)));
+            }
+        }
+        let count = individuals.len();
+        let places = Individual::places(&individuals);
+        let conference_places = conference_places(&individuals, |individual| {
+            (individual.conference, individual.score)
+        });
+        let mut placed: Vec<((usize, usize), Individual)> = places
+            .into_iter()
+            .zip(conference_places)
+            .zip(individuals)
+            .collect();
+        sort_placed(&mut placed, sort, Individual::compare);
+
+        placed
+            .iter()
+            .filter(|(_, individual)| find.as_ref().is_none_or(|find| individual.matches(find)))
+            .map(|((place, conference_place), individual)| {
+                IndividualRecord::new(
+                    individual,
+                    *place,
+                    *conference_place,
+                    percentile(*place, count),
+                )
+            })
+            .collect()
+    }
+
+    /// Teams in the order they're shown, with their places
+    pub fn team_records(&self, find: &Option<String>, sort: &[SortKey]) -> Vec<TeamRecord> {
+        let mut teams = self.teams.clone();
+        match self.kind {
+            ReportKind::Results => Team::sort_results(&mut teams),
+            ReportKind::Highscores => {
+                teams.sort_by(|a, b| b.score.cmp(&a.score).then(a.year.cmp(&b.year)));
+            }
+        }
+        let count = teams.len();
+        let places = Team::places(&teams);
+        let conference_places = conference_places(&teams, |team| (team.conference, team.score));
+        let mut placed: Vec<((usize, usize), Team)> = places
+            .into_iter()
+            .zip(conference_places)
+            .zip(teams)
+            .collect();
+        sort_placed(&mut placed, sort, Team::compare);
+
+        placed
+            .iter()
+            .filter(|(_, team)| find.as_ref().is_none_or(|find| team.matches(find)))
+            .map(|((place, conference_place), team)| {
+                TeamRecord::new(team, *place, *conference_place, percentile(*place, count))
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct IndividualRecord {
+    pub place: usize,
+    pub conference_place: usize,
+    pub percentile: f64,
+    pub name: String,
+    pub school: String,
+    pub conference: String,
+    #[serde(skip)]
+    pub conference_code: u8,
+    pub district: Option<u8>,
+    pub region: Option<u8>,
+    pub year: u16,
+    pub level: String,
+    pub score: Score,
+    pub points: Score,
+    pub advance: Option<&'static str>,
+    /// How the advancement is shown in tables
+    #[serde(skip)]
+    pub badge: Option<(String, Color)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub biology: Option<Score>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chemistry: Option<Score>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub physics: Option<Score>,
+    pub source: Option<String>,
+}
+
+impl IndividualRecord {
+    /// Whether `--find` picks this row, as with the result it came from
+    pub fn matches(&self, find: &str) -> bool {
+        self.name.contains(find) || self.school.contains(find)
+    }
+
+    /// One field as text
+    pub fn value(&self, column: Column) -> String {
+        match column {
+            Column::Place => self.place.to_string(),
+            Column::ConferencePlace => self.conference_place.to_string(),
+            Column::Percentile => format!("{:.1}", self.percentile),
+            Column::Name => self.name.clone(),
+            Column::School => self.school.clone(),
+            Column::Conference => self.conference.clone(),
+            Column::District => optional(self.district),
+            Column::Region => optional(self.region),
+            Column::Year => self.year.to_string(),
+            Column::Level => self.level.clone(),
+            Column::Score => self.score.to_string(),
+            Column::Points => self.points.to_string(),
+            Column::Advance => optional(self.advance),
+            Column::Biology => optional(self.biology),
+            Column::Chemistry => optional(self.chemistry),
+            Column::Physics => optional(self.physics),
+            Column::Prog | Column::Events => String::new(),
+            Column::Source => optional(self.source.as_ref()),
+        }
+    }
+
+    fn new(
+        individual: &Individual,
+        place: usize,
+        conference_place: usize,
+        percentile: f64,
+    ) -> Self {
+        Self {
+            place,
+            conference_place,
+            percentile,
+            name: individual.name.clone(),
+            school: individual.school.clone(),
+            conference: conference::label(individual.year, individual.conference),
+            conference_code: individual.conference,
+            district: individual.district,
+            region: individual.region.or(district_as_region(
+                individual.district,
+                individual.year,
+                individual.conference,
+            )),
+            year: individual.year,
+            level: individual.level.to_string(),
+            score: individual.score,
+            points: individual.points,
+            advance: individual.advance.as_ref().map(|advance| match advance {
+                AdvanceTypeIndividual::Indiv => "individual",
+                AdvanceTypeIndividual::Team => "team",
+                AdvanceTypeIndividual::Wild => "wildcard",
+            }),
+            badge: individual
+                .advance
+                .as_ref()
+                .map(|advance| theme().individual_badge(advance)),
+            biology: individual.get_biology(),
+            chemistry: individual.get_chemistry(),
+            physics: individual.get_physics(),
+            source: individual.source.as_ref().map(|source| source.url.clone()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct TeamRecord {
+    pub place: usize,
+    pub conference_place: usize,
+    pub percentile: f64,
+    pub school: String,
+    pub conference: String,
+    #[serde(skip)]
+    pub conference_code: u8,
+    pub district: Option<u8>,
+    pub region: Option<u8>,
+    pub year: u16,
+    pub level: String,
+    pub score: Score,
+    pub points: Score,
+    pub advance: Option<&'static str>,
+    /// How the advancement is shown in tables
+    #[serde(skip)]
+    pub badge: Option<(String, Color)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prog: Option<Score>,
+    /// Sweepstakes points by event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub events: Option<Vec<EventPoints>>,
+    pub source: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct EventPoints {
+    pub event: String,
+    pub points: Score,
+}
+
+impl TeamRecord {
+    /// Whether `--find` picks this row, as with the result it came from
+    pub fn matches(&self, find: &str) -> bool {
+        self.school.contains(find)
+    }
+
+    /// One field as text
+    pub fn value(&self, column: Column) -> String {
+        match column {
+            Column::Place => self.place.to_string(),
+            Column::ConferencePlace => self.conference_place.to_string(),
+            Column::Percentile => format!("{:.1}", self.percentile),
+            Column::School => self.school.clone(),
+            Column::Conference => self.conference.clone(),
+            Column::District => optional(self.district),
+            Column::Region => optional(self.region),
+            Column::Year => self.year.to_string(),
+            Column::Level => self.level.clone(),
+            Column::Score => self.score.to_string(),
+            Column::Points => self.points.to_string(),
+            Column::Advance => optional(self.advance),
+            Column::Prog => optional(self.prog),
+            Column::Events => self
+                .events
+                .as_ref()
+                .map(|events| {
+                    events
+                        .iter()
+                        .map(|event| format!("{} {}", event.event, event.points))
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })
+                .unwrap_or_default(),
+            Column::Source => optional(self.source.as_ref()),
+            Column::Name | Column::Biology | Column::Chemistry | Column::Physics => String::new(),
+        }
+    }
+
+    fn new(team: &Team, place: usize, conference_place: usize, percentile: f64) -> Self {
+        Self {
+            place,
+            conference_place,
+            percentile,
+            school: team.school.clone(),
+            conference: conference::label(team.year, team.conference),
+            conference_code: team.conference,
+            district: team.district,
+            region: team.region.or(district_as_region(
+                team.district,
+                team.year,
+                team.conference,
+            )),
+            year: team.year,
+            level: team.level.to_string(),
+            score: team.score,
+            points: team.points,
+            advance: team.advance.as_ref().map(|advance| match advance {
+                AdvanceTypeTeam::Advance => "advance",
+                AdvanceTypeTeam::Alternate => "wildcard",
+            }),
+            badge: team
+                .advance
+                .as_ref()
+                .map(|advance| theme().team_badge(advance)),
+            prog: team.get_prog(),
+            events: match &team.misc {
+                TeamMisc::Sweepstakes { events } => Some(
+                    events
+                        .iter()
+                        .map(|(subject, points)| EventPoints {
+                            event: String::from(subject.to_string()),
+                            points: *points,
+                        })
+                        .collect(),
+                ),
+                _ => None,
+            },
+            source: team.source.as_ref().map(|source| source.url.clone()),
+        }
+    }
+}
+
+pub fn emit(report: &Report, cli: &Cli) {
+    match cli.format {
+        Format::Text => text::print(report, cli),
+        Format::Json => json::print_document(report, cli),
+        Format::Jsonl => json::print_lines(report, cli),
+        Format::Csv => match spreadsheet::write_csvs(report, cli) {
+            Ok(paths) => {
+                for path in paths {
+                    eprintln!("Wrote {}", path.display());
+                }
+            }
+            Err(err) => eprintln!("{}", format!("Couldn't write CSV: {err}").red()),
+        },
+        Format::Markdown => deliver(cli, report, "md", markdown::render(report, cli)),
+        Format::Html => deliver(cli, report, "html", html::render(report, cli)),
+        Format::Xlsx => match spreadsheet::write_workbook(report, cli) {
+            Ok(path) => eprintln!("Wrote {}", path.display()),
+            Err(err) => eprintln!("{}", format!("Couldn't write workbook: {err}").red()),
+        },
+    }
+}
+
+/// Prints a rendered report, or writes it to `--output` when a directory is given
+fn deliver(cli: &Cli, report: &Report, extension: &str, contents: String) {
+    let Some(directory) = cli.output.as_deref() else {
+        print!("{contents}");
+        return;
+    };
+
+    let directory = std::path::Path::new(directory);
+    let path = directory.join(format!("{}.{extension}", file_stem(&report.subject)));
+    match std::fs::create_dir_all(directory).and_then(|_| std::fs::write(&path, contents)) {
+        Ok(()) => eprintln!("Wrote {}", path.display()),
+        Err(err) => eprintln!(
+            "{}",
+            format!("Couldn't write {}: {err}", path.display()).red()
+        ),
+    }
+}
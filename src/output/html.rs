@@ -0,0 +1,151 @@
+use colored::Color;
+
+use crate::{
+    cli::Cli,
+    conference,
+    output::{
+        Medal, Report,
+        tables::{Cell, tables, title},
+    },
+    theme::{rgb, theme},
+};
+
+const STYLE: &str = r#"
+body { font-family: system-ui, sans-serif; margin: 2rem; color: #222; }
+table { border-collapse: collapse; margin-bottom: 2rem; }
+th, td { padding: 0.25rem 0.75rem; border-bottom: 1px solid #ddd; text-align: left; }
+th { cursor: pointer; user-select: none; background: #f4f4f4; }
+th.asc::after { content: " \25B2"; }
+th.desc::after { content: " \25BC"; }
+td.number { text-align: right; font-variant-numeric: tabular-nums; }
+.tag, .badge { display: inline-block; padding: 0 0.4rem; border-radius: 0.25rem; font-size: 0.85em; font-weight: 600; }
+.tag { border: 1px solid #999; color: #000; }
+.badge { color: #fff; }
+.generated { color: #666; }
+tr.cutoff td { border-bottom: 2px dashed #cd3131; }
+"#;
+
+/// Sorts a table by the clicked column, toggling between ascending and descending
+const SCRIPT: &str = r#"
+document.querySelectorAll("table").forEach(function (table) {
+  table.querySelectorAll("th").forEach(function (header, index) {
+    header.addEventListener("click", function () {
+      var ascending = !header.classList.contains("asc");
+      table.querySelectorAll("th").forEach(function (th) { th.classList.remove("asc", "desc"); });
+      header.classList.add(ascending ? "asc" : "desc");
+      var body = table.tBodies[0];
+      var rows = Array.prototype.slice.call(body.rows);
+      rows.sort(function (a, b) {
+        var x = a.cells[index].textContent.trim();
+        var y = b.cells[index].textContent.trim();
+        var order;
+        if (x !== "" && y !== "" && !isNaN(x) && !isNaN(y)) {
+          order = parseFloat(x) - parseFloat(y);
+        } else {
+          order = x.localeCompare(y);
+        }
+        return ascending ? order : -order;
+      });
+      rows.forEach(function (row) { body.appendChild(row); });
+    });
+  });
+});
+"#;
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// The CSS color closest to a terminal color
+fn css(color: Color) -> String {
+    let (r, g, b) = rgb(color);
+    format!("#{r:02x}{g:02x}{b:02x}")
+}
+
+/// Renders the report as a self-contained HTML page with sortable columns
+pub fn render(report: &Report, cli: &Cli) -> String {
+    let title = escape(&title(report));
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    html.push_str(&format!(
+        "<p class=\"generated\">Generated {}</p>\n",
+        report.generated.format("%Y-%m-%d %H:%M:%S UTC")
+    ));
+
+    for table in tables(report, cli) {
+        html.push_str(&format!(
+            "<h2>{}</h2>\n<table>\n<thead>\n<tr>",
+            escape(&table.title)
+        ));
+        for column in table.columns.iter() {
+            html.push_str(&format!("<th>{}</th>", column.header()));
+        }
+        html.push_str("</tr>\n</thead>\n<tbody>\n");
+
+        for row in table.rows {
+            let mut classes = Vec::new();
+            let mut style = String::new();
+            if let Some(medal) = Medal::from_place(row.place) {
+                classes.push(medal.name());
+                style = format!(" style=\"background: {}\"", css(theme().medal_color(medal)));
+            }
+            if row.cutoff {
+                classes.push("cutoff");
+            }
+            if classes.is_empty() {
+                html.push_str(&format!("<tr{style}>"));
+            } else {
+                html.push_str(&format!("<tr class=\"{}\"{style}>", classes.join(" ")));
+            }
+            for cell in row.cells {
+                let cell = match cell {
+                    Cell::Text(text) => format!("<td>{}</td>", escape(&text)),
+                    Cell::Number(text) => format!("<td class=\"number\">{}</td>", escape(&text)),
+                    Cell::Conference(label, _) if label.is_empty() => String::from("<td></td>"),
+                    Cell::Conference(label, code) => {
+                        let background = theme()
+                            .conference_color(code)
+                            .map_or(String::from("#ffffff"), css);
+                        format!(
+                            "<td><span class=\"tag\" style=\"background: {}\">{}</span></td>",
+                            escape(&background),
+                            escape(&label)
+                        )
+                    }
+                    Cell::Badge(_, Some((badge, color))) => format!(
+                        "<td><span class=\"badge\" style=\"background: {}\">{}</span></td>",
+                        escape(&css(color)),
+                        escape(&badge)
+                    ),
+                    Cell::Badge(_, None) => String::from("<td></td>"),
+                };
+                html.push_str(&cell);
+            }
+            html.push_str("</tr>\n");
+        }
+        html.push_str("</tbody>\n</table>\n");
+    }
+
+    if cli.sources {
+        html.push_str("<h2>Sources</h2>\n<ul>\n");
+        for source in report.sources.iter() {
+            let url = escape(&source.url);
+            html.push_str(&format!(
+                "<li>{} {} {} {} (fetched {}): <a href=\"{url}\">{url}</a></li>\n",
+                source.year,
+                escape(&conference::label(source.year, source.conference)),
+                source.subject.to_string(),
+                source.level,
+                source.fetched.format("%Y-%m-%d %H:%M:%S UTC"),
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    html.push_str(&format!("<script>{SCRIPT}</script>\n</body>\n</html>\n"));
+    html
+}
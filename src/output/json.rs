@@ -0,0 +1,143 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::{
+    cli::Cli,
+    output::{Column, Report, ReportKind},
+    results::Source,
+};
+
+#[derive(Serialize)]
+struct Metadata<'a> {
+    kind: &'static str,
+    subject: &'a str,
+    generated: DateTime<Utc>,
+    sources: &'a [Arc<Source>],
+}
+
+#[derive(Serialize)]
+struct Document<'a> {
+    metadata: Metadata<'a>,
+    individuals: Vec<Value>,
+    teams: Vec<Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    parts: Vec<Value>,
+}
+
+fn metadata(report: &Report) -> Metadata<'_> {
+    Metadata {
+        kind: match report.kind {
+            ReportKind::Results => "results",
+            ReportKind::Highscores => "highscores",
+        },
+        subject: report.subject.to_string(),
+        generated: report.generated,
+        sources: &report.sources,
+    }
+}
+
+/// A record as a JSON object, keeping only the fields picked with `--columns`
+fn record<T: Serialize>(record: &T, columns: &[Column]) -> Map<String, Value> {
+    let Ok(Value::Object(mut object)) = serde_json::to_value(record) else {
+        return Map::new();
+    };
+    if !columns.is_empty() {
+        object.retain(|key, _| columns.iter().any(|column| column.key() == key));
+    }
+    object
+}
+
+/// Puts `fields` at the start of a record, e.g. what kind of record it is
+fn tagged(fields: &[(&str, &str)], record: Map<String, Value>) -> Value {
+    let mut object: Map<String, Value> = fields
+        .iter()
+        .map(|(key, value)| (key.to_string(), Value::from(*value)))
+        .collect();
+    object.extend(record);
+    Value::Object(object)
+}
+
+fn individuals(report: &Report, cli: &Cli) -> Vec<Map<String, Value>> {
+    report
+        .individual_records(&report.individuals, &cli.find, &cli.sort)
+        .iter()
+        .map(|individual| record(individual, &cli.columns))
+        .collect()
+}
+
+fn teams(report: &Report, cli: &Cli) -> Vec<Map<String, Value>> {
+    report
+        .team_records(&cli.find, &cli.sort)
+        .iter()
+        .map(|team| record(team, &cli.columns))
+        .collect()
+}
+
+fn parts(report: &Report, cli: &Cli) -> Vec<(&'static str, Map<String, Value>)> {
+    report
+        .parts
+        .iter()
+        .flat_map(|(part, individuals)| {
+            report
+                .individual_records(individuals, &cli.find, &cli.sort)
+                .iter()
+                .map(|individual| (part.key(), record(individual, &cli.columns)))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Prints the whole report as one JSON document
+pub fn print_document(report: &Report, cli: &Cli) {
+    let document = Document {
+        metadata: metadata(report),
+        individuals: individuals(report, cli)
+            .into_iter()
+            .map(Value::Object)
+            .collect(),
+        teams: teams(report, cli).into_iter().map(Value::Object).collect(),
+        parts: parts(report, cli)
+            .into_iter()
+            .map(|(part, individual)| tagged(&[("part", part)], individual))
+            .collect(),
+    };
+
+    match serde_json::to_string_pretty(&document) {
+        Ok(json) => println!("{json}"),
+        Err(err) => eprintln!("Couldn't write JSON: {err}"),
+    }
+}
+
+/// Prints the report as JSON Lines, metadata first and then one line per row,
+/// each tagged with what kind of record it is
+pub fn print_lines(report: &Report, cli: &Cli) {
+    let metadata = match serde_json::to_value(metadata(report)) {
+        Ok(Value::Object(metadata)) => metadata,
+        _ => Map::new(),
+    };
+
+    let lines =
+        std::iter::once(tagged(&[("record", "metadata")], metadata))
+            .chain(
+                individuals(report, cli)
+                    .into_iter()
+                    .map(|individual| tagged(&[("record", "individual")], individual)),
+            )
+            .chain(
+                teams(report, cli)
+                    .into_iter()
+                    .map(|team| tagged(&[("record", "team")], team)),
+            )
+            .chain(parts(report, cli).into_iter().map(|(part, individual)| {
+                tagged(&[("record", "part"), ("part", part)], individual)
+            }));
+    for line in lines {
+        match serde_json::to_string(&line) {
+            Ok(json) => println!("{json}"),
+            Err(err) => eprintln!("Couldn't write JSON: {err}"),
+        }
+    }
+}
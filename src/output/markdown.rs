@@ -0,0 +1,90 @@
+use crate::{
+    cli::Cli,
+    conference,
+    output::{
+        Column, Medal, Report,
+        tables::{Cell, tables, title},
+    },
+};
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+fn medal_emoji(medal: Medal) -> &'static str {
+    match medal {
+        Medal::Gold => "🥇",
+        Medal::Silver => "🥈",
+        Medal::Bronze => "🥉",
+    }
+}
+
+/// Renders the report as Markdown tables
+pub fn render(report: &Report, cli: &Cli) -> String {
+    let mut markdown = format!("# {}\n\n", title(report));
+    markdown.push_str(&format!(
+        "_Generated {}_\n",
+        report.generated.format("%Y-%m-%d %H:%M:%S UTC")
+    ));
+
+    for table in tables(report, cli) {
+        markdown.push_str(&format!("\n## {}\n\n", table.title));
+        let headers: Vec<&str> = table.columns.iter().map(Column::header).collect();
+        markdown.push_str(&format!("| {} |\n", headers.join(" | ")));
+
+        let alignments: Vec<&str> = table
+            .columns
+            .iter()
+            .map(|column| match column {
+                column if column.is_numeric() => "---:",
+                _ => "---",
+            })
+            .collect();
+        markdown.push_str(&format!("| {} |\n", alignments.join(" | ")));
+
+        for row in table.rows {
+            let medal = Medal::from_place(row.place);
+            let cells: Vec<String> = row
+                .cells
+                .iter()
+                .enumerate()
+                .map(|(index, cell)| {
+                    let text = match cell {
+                        Cell::Text(text) | Cell::Number(text) => escape(text),
+                        Cell::Conference(label, _) if label.is_empty() => String::new(),
+                        Cell::Conference(label, _) => format!("`{}`", escape(label)),
+                        Cell::Badge(_, Some((badge, _))) => format!("**{}**", escape(badge)),
+                        Cell::Badge(_, None) => String::new(),
+                    };
+                    // The place column carries the medal
+                    match medal {
+                        Some(medal) if index == 0 => format!("{} {text}", medal_emoji(medal)),
+                        _ => text,
+                    }
+                })
+                .collect();
+            markdown.push_str(&format!("| {} |\n", cells.join(" | ")));
+            if row.cutoff {
+                let mut cutoff = vec![String::new(); table.columns.len()];
+                cutoff[0] = String::from("_cutoff_");
+                markdown.push_str(&format!("| {} |\n", cutoff.join(" | ")));
+            }
+        }
+    }
+
+    if cli.sources {
+        markdown.push_str("\n## Sources\n\n");
+        for source in report.sources.iter() {
+            markdown.push_str(&format!(
+                "- {} {} {} {} (fetched {}): <{}>\n",
+                source.year,
+                conference::label(source.year, source.conference),
+                source.subject.to_string(),
+                source.level,
+                source.fetched.format("%Y-%m-%d %H:%M:%S UTC"),
+                source.url
+            ));
+        }
+    }
+    markdown
+}
@@ -0,0 +1,366 @@
+use std::path::{Path, PathBuf};
+
+use rust_xlsxwriter::{
+    Color as XlsxColor, ConditionalFormatFormula, Format, FormatBorder, Workbook, XlsxError,
+    utility,
+};
+
+use crate::{
+    advance::{AdvanceTypeIndividual, AdvanceTypeTeam},
+    cli::Cli,
+    output::{Column, Report, individual_columns, tables::title, team_columns},
+    request::Subject,
+    score::Score,
+    theme::{rgb, theme},
+};
+
+/// Columns every individual file has, unless `--columns` picks others
+pub fn default_individual_columns(subject: &Subject) -> Vec<Column> {
+    let mut columns = vec![
+        Column::Place,
+        Column::ConferencePlace,
+        Column::Percentile,
+        Column::Name,
+        Column::School,
+        Column::Conference,
+        Column::District,
+        Column::Region,
+        Column::Year,
+        Column::Level,
+        Column::Score,
+        Column::Points,
+        Column::Advance,
+    ];
+    if *subject == Subject::Science {
+        columns.extend([Column::Biology, Column::Chemistry, Column::Physics]);
+    }
+    columns.push(Column::Source);
+    columns
+}
+
+/// Columns every team file has, unless `--columns` picks others
+pub fn default_team_columns(subject: &Subject) -> Vec<Column> {
+    let mut columns = vec![
+        Column::Place,
+        Column::ConferencePlace,
+        Column::Percentile,
+        Column::School,
+        Column::Conference,
+        Column::District,
+        Column::Region,
+        Column::Year,
+        Column::Level,
+        Column::Score,
+        Column::Points,
+        Column::Advance,
+    ];
+    if *subject == Subject::ComputerScience {
+        columns.push(Column::Prog);
+    }
+    if *subject == Subject::Sweepstakes {
+        columns.push(Column::Events);
+    }
+    columns.push(Column::Source);
+    columns
+}
+
+/// One table of an export: a CSV file or a section of a subject's workbook sheet
+struct Section {
+    /// Used in file names, e.g. "individuals"
+    key: String,
+    /// Used as the section title, e.g. "Individuals"
+    name: String,
+    columns: Vec<Column>,
+    rows: Vec<Vec<String>>,
+    /// How each row advanced, if it did
+    advance: Vec<Option<&'static str>>,
+    /// The winner's name or school and score
+    leader: Option<(String, Score)>,
+}
+
+/// The individual, part and team tables, every row included
+fn sections(report: &Report, cli: &Cli) -> Vec<Section> {
+    let individual_columns =
+        individual_columns(&cli.columns, default_individual_columns(&report.subject));
+    let team_columns = team_columns(&cli.columns, default_team_columns(&report.subject));
+
+    let individual_section = |key: &str, name: &str, individuals| {
+        let records = report.individual_records(individuals, &cli.find, &cli.sort);
+        Section {
+            key: key.to_string(),
+            name: name.to_string(),
+            rows: records
+                .iter()
+                .map(|record| {
+                    individual_columns
+                        .iter()
+                        .map(|column| record.value(*column))
+                        .collect()
+                })
+                .collect(),
+            advance: records.iter().map(|record| record.advance).collect(),
+            leader: records
+                .iter()
+                .min_by_key(|record| record.place)
+                .map(|record| (record.name.clone(), record.score)),
+            columns: individual_columns.clone(),
+        }
+    };
+
+    let mut sections = vec![individual_section(
+        "individuals",
+        "Individuals",
+        &report.individuals,
+    )];
+    for (part, individuals) in report.parts.iter() {
+        sections.push(individual_section(part.key(), part.name(), individuals));
+    }
+
+    let records = report.team_records(&cli.find, &cli.sort);
+    sections.push(Section {
+        key: String::from("teams"),
+        name: String::from("Teams"),
+        rows: records
+            .iter()
+            .map(|record| {
+                team_columns
+                    .iter()
+                    .map(|column| record.value(*column))
+                    .collect()
+            })
+            .collect(),
+        advance: records.iter().map(|record| record.advance).collect(),
+        leader: records
+            .iter()
+            .min_by_key(|record| record.place)
+            .map(|record| (record.school.clone(), record.score)),
+        columns: team_columns,
+    });
+    sections
+}
+
+/// One sheet per subject, named after it, holding that subject's non-empty sections.
+/// Rankings and sweepstakes also get a sheet for each event they were totalled from.
+fn sheets(report: &Report, cli: &Cli) -> Vec<(String, Vec<Section>)> {
+    std::iter::once(report)
+        .chain(report.events.iter())
+        .map(|report| {
+            let sections: Vec<Section> = sections(report, cli)
+                .into_iter()
+                .filter(|section| !section.rows.is_empty())
+                .collect();
+            (report.subject.to_string().to_string(), sections)
+        })
+        .filter(|(_, sections)| !sections.is_empty())
+        .collect()
+}
+
+fn write_csv(path: &Path, section: &Section) -> Result<(), String> {
+    let mut writer = csv::Writer::from_path(path).map_err(|err| err.to_string())?;
+    writer
+        .write_record(section.columns.iter().map(Column::key))
+        .map_err(|err| err.to_string())?;
+    for row in section.rows.iter() {
+        writer.write_record(row).map_err(|err| err.to_string())?;
+    }
+    writer.flush().map_err(|err| err.to_string())
+}
+
+/// File names start with the subject, e.g. "number_sense"
+pub fn file_stem(subject: &Subject) -> String {
+    subject.to_string().to_lowercase().replace(' ', "_")
+}
+
+/// The `--output` directory, created if it doesn't exist
+fn directory(cli: &Cli) -> Result<PathBuf, String> {
+    let directory = PathBuf::from(cli.output.as_deref().unwrap_or("."));
+    std::fs::create_dir_all(&directory).map_err(|err| err.to_string())?;
+    Ok(directory)
+}
+
+/// Writes the individual, team and (for Science) part tables to separate CSV files
+pub fn write_csvs(report: &Report, cli: &Cli) -> Result<Vec<PathBuf>, String> {
+    let directory = directory(cli)?;
+    let stem = file_stem(&report.subject);
+    let mut written = Vec::new();
+
+    for section in sections(report, cli) {
+        let path = directory.join(format!("{stem}_{}.csv", section.key));
+        write_csv(&path, &section).map_err(|err| format!("{}: {err}", path.display()))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// A light version of a theme color, so text on top of it stays readable
+fn fill(color: colored::Color) -> XlsxColor {
+    let (r, g, b) = rgb(color);
+    let lighten = |channel: u8| (channel as u32 + 3 * 255) / 4;
+    XlsxColor::RGB((lighten(r) << 16) | (lighten(g) << 8) | lighten(b))
+}
+
+/// The fill for each kind of advancement, matching the terminal badges
+fn advance_fills() -> Vec<(&'static str, XlsxColor)> {
+    let theme = theme();
+    vec![
+        (
+            "individual",
+            fill(theme.individual_badge(&AdvanceTypeIndividual::Indiv).1),
+        ),
+        (
+            "team",
+            fill(theme.individual_badge(&AdvanceTypeIndividual::Team).1),
+        ),
+        (
+            "wildcard",
+            fill(theme.individual_badge(&AdvanceTypeIndividual::Wild).1),
+        ),
+        (
+            "advance",
+            fill(theme.team_badge(&AdvanceTypeTeam::Advance).1),
+        ),
+    ]
+}
+
+/// Writes a subject's sections one under another, each with a title and header row.
+/// The first section's header stays frozen and gets the sheet's autofilter.
+fn add_sheet(workbook: &mut Workbook, name: &str, sections: &[Section]) -> Result<(), XlsxError> {
+    let bold = Format::new().set_bold();
+    let header = Format::new()
+        .set_bold()
+        .set_border_bottom(FormatBorder::Thin);
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(name)?;
+
+    let mut top = 0;
+    for (index, section) in sections.iter().enumerate() {
+        worksheet.write_string_with_format(top, 0, &section.name, &bold)?;
+        let header_row = top + 1;
+        for (column_index, column) in section.columns.iter().enumerate() {
+            worksheet.write_string_with_format(
+                header_row,
+                column_index as u16,
+                column.header(),
+                &header,
+            )?;
+        }
+        for (row, values) in section.rows.iter().enumerate() {
+            let row = header_row + row as u32 + 1;
+            for (column_index, (column, value)) in section.columns.iter().zip(values).enumerate() {
+                let column_index = column_index as u16;
+                match value.parse::<f64>() {
+                    Ok(number) if column.is_numeric() => {
+                        worksheet.write_number(row, column_index, number)?
+                    }
+                    _ => worksheet.write_string(row, column_index, value)?,
+                };
+            }
+        }
+
+        let first_row = header_row + 1;
+        let last_row = header_row + section.rows.len() as u32;
+        let last_column = section.columns.len().saturating_sub(1) as u16;
+        if index == 0 {
+            worksheet.set_freeze_panes(first_row, 0)?;
+            worksheet.autofilter(header_row, 0, last_row, last_column)?;
+        }
+
+        // Advancing rows are shaded by the advance column, so they follow the rows when re-sorted
+        if let Some(advance) = section
+            .columns
+            .iter()
+            .position(|column| *column == Column::Advance)
+        {
+            let advance = utility::column_number_to_name(advance as u16);
+            for (status, color) in advance_fills() {
+                let rule = ConditionalFormatFormula::new()
+                    .set_rule(format!("=${advance}{}=\"{status}\"", first_row + 1).as_str())
+                    .set_format(Format::new().set_background_color(color));
+                worksheet.add_conditional_format(first_row, 0, last_row, last_column, &rule)?;
+            }
+        }
+
+        top = last_row + 2;
+    }
+
+    worksheet.autofit();
+    Ok(())
+}
+
+fn add_summary(
+    workbook: &mut Workbook,
+    report: &Report,
+    sheets: &[(String, Vec<Section>)],
+) -> Result<(), XlsxError> {
+    let bold = Format::new().set_bold();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Summary")?;
+
+    worksheet.write_string_with_format(0, 0, title(report), &bold)?;
+    worksheet.write_string(1, 0, "Subject")?;
+    worksheet.write_string(1, 1, report.subject.to_string())?;
+    worksheet.write_string(2, 0, "Generated")?;
+    worksheet.write_string(
+        2,
+        1,
+        report.generated.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+    )?;
+
+    for (index, header) in ["Sheet", "Table", "Rows", "Advancing", "Leader", "Top score"]
+        .iter()
+        .enumerate()
+    {
+        worksheet.write_string_with_format(4, index as u16, *header, &bold)?;
+    }
+    let mut row = 5;
+    for (name, sections) in sheets {
+        for section in sections {
+            worksheet.write_string(row, 0, name)?;
+            worksheet.write_string(row, 1, &section.name)?;
+            worksheet.write_number(row, 2, section.rows.len() as u32)?;
+            worksheet.write_number(
+                row,
+                3,
+                section
+                    .advance
+                    .iter()
+                    .filter(|advance| advance.is_some())
+                    .count() as u32,
+            )?;
+            if let Some((leader, score)) = &section.leader {
+                worksheet.write_string(row, 4, leader)?;
+                worksheet.write_number(row, 5, score.as_f64())?;
+            }
+            row += 1;
+        }
+    }
+
+    if !report.sources.is_empty() {
+        row += 1;
+        worksheet.write_string_with_format(row, 0, "Sources", &bold)?;
+        for source in report.sources.iter() {
+            row += 1;
+            worksheet.write_string(row, 0, &source.url)?;
+        }
+    }
+
+    worksheet.set_freeze_panes(5, 0)?;
+    worksheet.autofit();
+    Ok(())
+}
+
+/// Writes a workbook with a summary sheet and one sheet per subject
+pub fn write_workbook(report: &Report, cli: &Cli) -> Result<PathBuf, String> {
+    let path = directory(cli)?.join(format!("{}.xlsx", file_stem(&report.subject)));
+    let sheets = sheets(report, cli);
+
+    let mut workbook = Workbook::new();
+    add_summary(&mut workbook, report, &sheets).map_err(|err| err.to_string())?;
+    for (name, sections) in sheets.iter() {
+        add_sheet(&mut workbook, name, sections).map_err(|err| err.to_string())?;
+    }
+    workbook
+        .save(&path)
+        .map_err(|err| format!("{}: {err}", path.display()))?;
+    Ok(path)
+}
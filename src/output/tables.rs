@@ -0,0 +1,273 @@
+use colored::Color;
+
+use crate::{
+    cli::Cli,
+    individual::Individual,
+    output::{Column, Report, ReportKind, individual_columns, team_columns},
+    request::Subject,
+    sort::by_score,
+};
+
+/// One cell of a rendered table
+pub enum Cell {
+    Text(String),
+    /// Right aligned when rendered
+    Number(String),
+    /// A conference tag, with the conference code its color comes from
+    Conference(String, u8),
+    /// An advancement badge, with the advancement as it's exported (e.g. "wildcard")
+    Badge(String, Option<(String, Color)>),
+}
+
+impl Cell {
+    fn new(column: Column, value: String, code: u8, badge: &Option<(String, Color)>) -> Self {
+        match column {
+            Column::Conference => Self::Conference(value, code),
+            Column::Advance => Self::Badge(value, badge.clone()),
+            column if column.is_numeric() => Self::Number(value),
+            _ => Self::Text(value),
+        }
+    }
+}
+
+pub struct Row {
+    pub place: usize,
+    pub cells: Vec<Cell>,
+    /// Whether the cutoff line goes under this row, when each meet is shown on its own
+    pub cutoff: bool,
+}
+
+/// A table as it's shown in the terminal, for the Markdown and HTML reports
+pub struct Table {
+    pub title: String,
+    pub columns: Vec<Column>,
+    pub rows: Vec<Row>,
+}
+
+/// Whether a row would be shown in the terminal with `--individual-positions`/`--team-positions`
+fn shown(place: usize, positions: usize, cli: &Cli) -> bool {
+    cli.find.is_some() || positions == 0 || place <= positions
+}
+
+fn default_individual_columns(report: &Report) -> Vec<Column> {
+    let mut columns = vec![Column::Place];
+    if report.mixes_conferences() {
+        columns.extend([Column::ConferencePlace, Column::Percentile]);
+    }
+    columns.extend([
+        Column::Name,
+        Column::School,
+        Column::Conference,
+        Column::District,
+        Column::Region,
+    ]);
+    if report.kind == ReportKind::Highscores {
+        columns.push(Column::Year);
+    }
+    columns.extend([Column::Score, Column::Advance]);
+    columns
+}
+
+fn default_team_columns(report: &Report) -> Vec<Column> {
+    let mut columns = vec![Column::Place];
+    if report.mixes_conferences() {
+        columns.extend([Column::ConferencePlace, Column::Percentile]);
+    }
+    columns.extend([
+        Column::School,
+        Column::Conference,
+        Column::District,
+        Column::Region,
+    ]);
+    if report.kind == ReportKind::Highscores {
+        columns.push(Column::Year);
+    }
+    columns.push(Column::Score);
+    if report.subject == Subject::ComputerScience {
+        columns.push(Column::Prog);
+    }
+    if report.subject == Subject::Sweepstakes {
+        columns.push(Column::Events);
+    } else {
+        columns.push(Column::Advance);
+    }
+    columns
+}
+
+fn positions(report: &Report, positions: Option<usize>) -> usize {
+    positions.unwrap_or(if report.kind == ReportKind::Highscores {
+        10
+    } else {
+        25
+    })
+}
+
+/// Where the cutoff line goes when each meet is shown on its own: the index of the last
+/// advancing record, found before any are left out. It only means anything in score order.
+fn last_advancing(cutoff: bool, advancing: &[bool], cli: &Cli) -> Option<usize> {
+    if !cutoff || !by_score(&cli.sort) {
+        return None;
+    }
+    advancing.iter().rposition(|advancing| *advancing)
+}
+
+/// Marks the cutoff under the last shown row at or above the last advancing record
+fn mark_cutoff(mut rows: Vec<(usize, Row)>, last_advancing: Option<usize>) -> Vec<Row> {
+    if let Some(last_advancing) = last_advancing
+        && let Some(row) = rows
+            .iter_mut()
+            .rev()
+            .find(|(index, _)| *index <= last_advancing)
+    {
+        row.1.cutoff = true;
+    }
+    rows.into_iter().map(|(_, row)| row).collect()
+}
+
+fn individual_table(
+    title: String,
+    individuals: &[Individual],
+    report: &Report,
+    cli: &Cli,
+    cutoff: bool,
+) -> Table {
+    let positions = positions(report, cli.individual_positions);
+    let columns = individual_columns(&cli.columns, default_individual_columns(report));
+
+    let records = report.individual_records(individuals, &None, &cli.sort);
+    let advancing: Vec<bool> = records
+        .iter()
+        .map(|record| record.advance.is_some())
+        .collect();
+    let last_advancing = last_advancing(cutoff, &advancing, cli);
+    let rows = records
+        .into_iter()
+        .enumerate()
+        .filter(|(_, record)| cli.find.as_ref().is_none_or(|find| record.matches(find)))
+        .filter(|(_, record)| shown(record.place, positions, cli))
+        .map(|(index, record)| {
+            let row = Row {
+                place: record.place,
+                cutoff: false,
+                cells: columns
+                    .iter()
+                    .map(|column| {
+                        Cell::new(
+                            *column,
+                            record.value(*column),
+                            record.conference_code,
+                            &record.badge,
+                        )
+                    })
+                    .collect(),
+            };
+            (index, row)
+        })
+        .collect();
+
+    Table {
+        title,
+        columns,
+        rows: mark_cutoff(rows, last_advancing),
+    }
+}
+
+fn team_table(title: String, report: &Report, cli: &Cli, cutoff: bool) -> Table {
+    let positions = positions(report, cli.team_positions);
+    let columns = team_columns(&cli.columns, default_team_columns(report));
+    let computer_science = report.subject == Subject::ComputerScience;
+
+    let records = report.team_records(&None, &cli.sort);
+    let advancing: Vec<bool> = records
+        .iter()
+        .map(|record| record.advance.is_some())
+        .collect();
+    let last_advancing = last_advancing(cutoff, &advancing, cli);
+    let rows = records
+        .into_iter()
+        .enumerate()
+        .filter(|(_, record)| cli.find.as_ref().is_none_or(|find| record.matches(find)))
+        .filter(|(_, record)| shown(record.place, positions, cli))
+        .map(|(index, record)| {
+            let row = Row {
+                place: record.place,
+                cutoff: false,
+                cells: columns
+                    .iter()
+                    .map(|column| {
+                        let mut value = record.value(*column);
+                        if *column == Column::Prog && computer_science && value.is_empty() {
+                            value = String::from("N/A");
+                        }
+                        Cell::new(*column, value, record.conference_code, &record.badge)
+                    })
+                    .collect(),
+            };
+            (index, row)
+        })
+        .collect();
+
+    Table {
+        title,
+        columns,
+        rows: mark_cutoff(rows, last_advancing),
+    }
+}
+
+/// The tables the terminal would show for this report, in the same order.
+/// With `--group-by`, each meet gets its own tables with its advancing cutoff marked.
+pub fn tables(report: &Report, cli: &Cli) -> Vec<Table> {
+    let Some(group_by) = cli.group_by else {
+        return meet_tables(report, cli, false);
+    };
+
+    let mut tables = Vec::new();
+    for (label, group) in report.groups(group_by) {
+        for mut table in meet_tables(&group, cli, true) {
+            table.title = format!("{label} {}", table.title);
+            tables.push(table);
+        }
+    }
+    tables
+}
+
+/// A meet's tables, with the advancing cutoff marked if `cutoff`
+fn meet_tables(report: &Report, cli: &Cli, cutoff: bool) -> Vec<Table> {
+    let mut tables = Vec::new();
+    let subject = report.subject.to_string();
+
+    if !report.individuals.is_empty() {
+        let title = match report.kind {
+            ReportKind::Results => String::from("Individual Total Scores"),
+            ReportKind::Highscores => format!("{subject} Individual Results"),
+        };
+        tables.push(individual_table(
+            title,
+            &report.individuals,
+            report,
+            cli,
+            cutoff,
+        ));
+
+        for (part, individuals) in report.parts.iter() {
+            let title = format!("Individual {} Scores", part.name());
+            tables.push(individual_table(title, individuals, report, cli, cutoff));
+        }
+    }
+    if !report.teams.is_empty() {
+        let title = match report.kind {
+            ReportKind::Results => String::from("Team Scores"),
+            ReportKind::Highscores => format!("{subject} Team Results"),
+        };
+        tables.push(team_table(title, report, cli, cutoff));
+    }
+    tables
+}
+
+/// The title of the whole report
+pub fn title(report: &Report) -> String {
+    match report.kind {
+        ReportKind::Results => format!("{} Results", report.subject.to_string()),
+        ReportKind::Highscores => format!("{} Highscores", report.subject.to_string()),
+    }
+}
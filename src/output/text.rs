@@ -0,0 +1,199 @@
+use colored::{ColoredString, Colorize};
+
+use crate::{
+    cli::Cli,
+    conference,
+    individual::Individual,
+    output::{
+        Column, Report, ReportKind, column_widths, ordinal, print_header,
+        tables::{Cell, tables},
+    },
+    overall,
+    team::Team,
+    theme::theme,
+};
+
+/// Prints the report as colored tables
+pub fn print(report: &Report, cli: &Cli) {
+    if cli.plain {
+        print_plain(report, cli);
+    } else if !cli.columns.is_empty()
+        || (report.kind == ReportKind::Highscores && !cli.sort.is_empty())
+    {
+        print_columns(report, cli);
+    } else if let Some(group_by) = cli.group_by {
+        for (label, group) in report.groups(group_by) {
+            println!("{}", format!("{label}:").bold());
+            print_tables(&group, cli, true);
+            println!();
+        }
+    } else {
+        print_tables(report, cli, false);
+    }
+
+    if cli.sources {
+        println!();
+        println!("Sources:");
+        for source in report.sources.iter() {
+            println!(
+                "{} {} {} {} (fetched {}): {}",
+                source.year,
+                conference::label(source.year, source.conference),
+                source.subject.to_string(),
+                source.level,
+                source.fetched.format("%Y-%m-%d %H:%M:%S UTC"),
+                source.url
+            );
+        }
+    }
+}
+
+/// Prints the usual layout, with a line under the last advancing rows if `cutoff`
+fn print_tables(report: &Report, cli: &Cli, cutoff: bool) {
+    if report.kind == ReportKind::Highscores {
+        overall::display_highscores(
+            &report.subject,
+            report.individuals.clone(),
+            report.teams.clone(),
+            cli,
+        );
+    } else {
+        if !report.individuals.is_empty() {
+            println!("Individual Total Scores:");
+            Individual::display_results(
+                report.individuals.clone(),
+                cli.individual_positions.unwrap_or(25),
+                &cli.find,
+                &cli.sort,
+                cutoff,
+            );
+            println!();
+            for (part, individuals) in report.parts.iter() {
+                println!("Individual {} Scores:", part.name());
+                Individual::display_results(
+                    individuals.clone(),
+                    cli.individual_positions.unwrap_or(25),
+                    &cli.find,
+                    &cli.sort,
+                    cutoff,
+                );
+                println!();
+            }
+        }
+        if !report.teams.is_empty() {
+            println!("Team Scores:");
+            Team::display_results(
+                report.teams.clone(),
+                report.subject.clone(),
+                cli.team_positions.unwrap_or(25),
+                &cli.find,
+                &cli.sort,
+                cutoff,
+            );
+        }
+    }
+}
+
+/// Prints one line per row with every field labeled and nothing conveyed by color or alignment
+fn print_plain(report: &Report, cli: &Cli) {
+    for table in tables(report, cli) {
+        println!("{}:", table.title);
+
+        for (index, row) in table.rows.iter().enumerate() {
+            let tied = table
+                .rows
+                .iter()
+                .enumerate()
+                .any(|(other, x)| other != index && x.place == row.place);
+            let mut fields = vec![if tied {
+                format!("tied {}", ordinal(row.place))
+            } else {
+                ordinal(row.place)
+            }];
+
+            for (column, cell) in table.columns.iter().zip(row.cells.iter()) {
+                let value = match cell {
+                    Cell::Text(text)
+                    | Cell::Number(text)
+                    | Cell::Conference(text, _)
+                    | Cell::Badge(text, _) => text.clone(),
+                };
+                if value.is_empty() {
+                    continue;
+                }
+                match column {
+                    Column::Place => {}
+                    Column::ConferencePlace => fields.push(format!(
+                        "conference place: {}",
+                        value.parse().map(ordinal).unwrap_or(value)
+                    )),
+                    Column::Percentile => fields.push(format!("percentile: {value}%")),
+                    Column::Advance => fields.push(format!("advances: {value}")),
+                    column => fields.push(format!("{}: {value}", column.key().replace('_', " "))),
+                }
+            }
+            println!("{}", fields.join("; "));
+
+            if row.cutoff {
+                println!("advancement cutoff");
+            }
+        }
+        println!();
+    }
+}
+
+/// Prints the columns picked with `--columns` as aligned tables
+fn print_columns(report: &Report, cli: &Cli) {
+    let theme = theme();
+    for table in tables(report, cli) {
+        println!("{}:", table.title);
+
+        let text = |cell: &Cell| match cell {
+            Cell::Text(text) | Cell::Number(text) | Cell::Conference(text, _) => text.clone(),
+            Cell::Badge(_, badge) => badge
+                .as_ref()
+                .map(|(label, _)| label.clone())
+                .unwrap_or_default(),
+        };
+        let headers: Vec<&str> = table.columns.iter().map(|column| column.header()).collect();
+        let texts: Vec<Vec<String>> = table
+            .rows
+            .iter()
+            .map(|row| row.cells.iter().map(text).collect())
+            .collect();
+        let widths = column_widths(&headers, &texts);
+        print_header(&headers, &widths);
+
+        for row in table.rows.iter() {
+            let cells: Vec<String> = row
+                .cells
+                .iter()
+                .zip(widths.iter())
+                .enumerate()
+                .map(|(index, (cell, width))| {
+                    let padded = match cell {
+                        Cell::Number(text) => format!("{text:>width$}"),
+                        cell => format!("{:width$}", text(cell)),
+                    };
+                    let colored: ColoredString = match cell {
+                        // The first column carries the medal, like the place in the usual layout
+                        _ if index == 0 => theme.medal(padded, row.place),
+                        Cell::Conference(_, code) => match theme.conference_color(*code) {
+                            Some(color) => padded.color(color),
+                            None => padded.into(),
+                        },
+                        Cell::Badge(_, Some((_, color))) => padded.color(*color),
+                        _ => padded.into(),
+                    };
+                    colored.to_string()
+                })
+                .collect();
+            println!("{}", cells.join("  ").trim_end());
+            if row.cutoff {
+                let width = widths.iter().sum::<usize>() + 2 * widths.len().saturating_sub(1);
+                println!("{}", "-".repeat(width));
+            }
+        }
+        println!();
+    }
+}
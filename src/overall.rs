@@ -1,23 +1,31 @@
+use std::ops::RangeInclusive;
 use std::sync::{Arc, Mutex};
 
 use chrono::Datelike;
-use colored::{ColoredString, Colorize};
+use colored::ColoredString;
 
 use crate::{
     Individual,
     cli::Cli,
+    competitor::{CompetitorKey, Matching},
+    conference,
     individual::IndividualMisc,
     overall,
     request::{RequestFields, Subject},
+    results::ResultSet,
+    score::Score,
     scrape::scrape_subject,
+    sweepstakes::{self, Event},
     team::{Team, TeamMisc},
+    theme::theme,
 };
 
 pub fn rankings(
     request_fields: RequestFields,
     conferences: Vec<u8>,
     mute: bool,
-) -> Option<(Vec<Individual>, Vec<Team>)> {
+    matching: Matching,
+) -> Option<ResultSet> {
     let supported_subjects = [
         Subject::Accounting,
         Subject::ComputerScience,
@@ -30,21 +38,33 @@ pub fn rankings(
     ];
     let mut individual_results: Vec<Individual> = Vec::new();
     let mut team_results: Vec<Team> = Vec::new();
+    let mut sources = Vec::new();
+    let mut events = Vec::new();
     for subject in supported_subjects {
         let mut fields = request_fields.clone();
-        fields.subject = subject;
-        let results = scrape_subject(fields.clone(), conferences.clone(), mute);
-        if results.is_none() {
+        fields.subject = subject.clone();
+        let Some(mut results) = scrape_subject(fields.clone(), conferences.clone(), mute) else {
             continue;
-        }
-        let (indiv, team) = results.unwrap();
+        };
+        events.push((
+            subject,
+            ResultSet {
+                individuals: results.individuals.clone(),
+                teams: results.teams.clone(),
+                sources: results.sources.clone(),
+                events: Vec::new(),
+            },
+        ));
+        sources.append(&mut results.sources);
+        let (indiv, team) = (results.individuals, results.teams);
 
         let (indiv_points, team_points) = calculate_rankings(indiv, team, fields.subject);
 
         for indiv in indiv_points {
+            let key = CompetitorKey::individual(&indiv, matching);
             let mut found = false;
             for result in &mut individual_results {
-                if result.name == indiv.name {
+                if CompetitorKey::individual(result, matching) == key {
                     found = true;
                     result.score += indiv.score;
                 }
@@ -55,9 +75,10 @@ pub fn rankings(
         }
 
         for team in team_points {
+            let key = CompetitorKey::team(&team, matching);
             let mut found = false;
             for result in &mut team_results {
-                if result.school == team.school {
+                if CompetitorKey::team(result, matching) == key {
                     found = true;
                     result.score += team.score;
                 }
@@ -70,13 +91,18 @@ pub fn rankings(
         if fields.district.is_some() {
             use std::{thread, time};
 
-            println!("Pausing to (hopefully) prevent rate limiting");
+            eprintln!("Pausing to (hopefully) prevent rate limiting");
             let second = time::Duration::from_millis(500);
 
             thread::sleep(second);
         }
     }
-    Some((individual_results, team_results))
+    Some(ResultSet {
+        individuals: individual_results,
+        teams: team_results,
+        sources,
+        events,
+    })
 }
 
 fn calculate_rankings(
@@ -102,7 +128,7 @@ fn calculate_rankings(
         let position = place + 1;
         let points = 5000.0 / (position as f32 + 4.0);
         let mut individual_copy = individual.clone();
-        individual_copy.score = points as i16;
+        individual_copy.score = Score::from(points as i64);
         individual_copy.misc = IndividualMisc::Normal;
         indiv_points.push(individual_copy);
     }
@@ -110,62 +136,62 @@ fn calculate_rankings(
         let position = place + 1;
         let points = 5000.0 / (position as f32 + 4.0);
         let mut team_copy = team.clone();
-        team_copy.score = points as i16;
+        team_copy.score = Score::from(points as i64);
         team_copy.misc = TeamMisc::Normal;
         team_points.push(team_copy);
     }
 
     if subject == Subject::Science {
         indiv.sort_by(|a, b| {
-            let a_score = a.get_biology().unwrap_or(-120);
-            let b_score = b.get_biology().unwrap_or(-120);
+            let a_score = a.get_biology().unwrap_or(Score::from(-120));
+            let b_score = b.get_biology().unwrap_or(Score::from(-120));
             b_score.cmp(&a_score)
         });
 
         for (place, individual) in indiv.iter().enumerate() {
-            if individual.score == -120 {
+            if individual.score == Score::from(-120) {
                 break;
             }
             let position = place + 1;
             let points = 1000.0 / (position as f32 + 4.0);
             let mut individual_copy = individual.clone();
-            individual_copy.score = points as i16;
+            individual_copy.score = Score::from(points as i64);
             individual_copy.misc = IndividualMisc::Normal;
             indiv_points.push(individual_copy);
         }
 
         indiv.sort_by(|a, b| {
-            let a_score = a.get_chemistry().unwrap_or(-120);
-            let b_score = b.get_chemistry().unwrap_or(-120);
+            let a_score = a.get_chemistry().unwrap_or(Score::from(-120));
+            let b_score = b.get_chemistry().unwrap_or(Score::from(-120));
             b_score.cmp(&a_score)
         });
 
         for (place, individual) in indiv.iter().enumerate() {
-            if individual.score == -120 {
+            if individual.score == Score::from(-120) {
                 break;
             }
             let position = place + 1;
             let points = 1000.0 / (position as f32 + 4.0);
             let mut individual_copy = individual.clone();
-            individual_copy.score = points as i16;
+            individual_copy.score = Score::from(points as i64);
             individual_copy.misc = IndividualMisc::Normal;
             indiv_points.push(individual_copy);
         }
 
         indiv.sort_by(|a, b| {
-            let a_score = a.get_physics().unwrap_or(-120);
-            let b_score = b.get_physics().unwrap_or(-120);
+            let a_score = a.get_physics().unwrap_or(Score::from(-120));
+            let b_score = b.get_physics().unwrap_or(Score::from(-120));
             b_score.cmp(&a_score)
         });
 
         for (place, individual) in indiv.iter().enumerate() {
-            if individual.score == -120 {
+            if individual.score == Score::from(-120) {
                 break;
             }
             let position = place + 1;
             let points = 1000.0 / (position as f32 + 4.0);
             let mut individual_copy = individual.clone();
-            individual_copy.score = points as i16;
+            individual_copy.score = Score::from(points as i64);
             individual_copy.misc = IndividualMisc::Normal;
             indiv_points.push(individual_copy);
         }
@@ -178,160 +204,95 @@ pub fn sweepstakes(
     request_fields: RequestFields,
     conferences: Vec<u8>,
     mute: bool,
-) -> Option<(Vec<Individual>, Vec<Team>)> {
-    let supported_subjects = [
-        Subject::Accounting,
-        Subject::ComputerApplications,
-        Subject::CurrentEvents,
-        Subject::ComputerScience,
-        Subject::Calculator,
-        Subject::Spelling,
-        Subject::Science,
-        Subject::SocialStudies,
-        Subject::Mathematics,
-        Subject::NumberSense,
-    ];
-    let mut individual_results: Vec<Individual> = Vec::new();
-    let mut team_results: Vec<Team> = Vec::new();
-    for subject in supported_subjects {
-        if subject == Subject::ComputerApplications && request_fields.year > 2024 {
-            // Computer Apps is discontinued
-            continue;
-        }
+    matching: Matching,
+) -> Option<ResultSet> {
+    let mut events = Vec::new();
+    let mut sources = Vec::new();
+    for subject in Subject::events(request_fields.year) {
         let mut fields = request_fields.clone();
         fields.subject = subject.clone();
-        let results = scrape_subject(fields.clone(), conferences.clone(), mute);
-        if results.is_none() {
-            continue;
-        }
-        let (mut indiv, mut team) = results.unwrap();
-
-        indiv.sort_by(|a, b| {
-            let a_score = a.score;
-            let b_score = b.score;
-            b_score.cmp(&a_score)
-        });
-        team.sort_by(|a, b| {
-            let a_score = a.score;
-            let b_score = b.score;
-            b_score.cmp(&a_score)
-        });
-
-        if indiv.is_empty() {
+        let Some(mut results) = scrape_subject(fields.clone(), conferences.clone(), mute) else {
             continue;
-        }
-
-        let indiv_ties = Individual::get_ties(indiv.clone());
-        let team_ties = Team::get_ties(team.clone());
-
-        const INDIV_POINTS: [f32; 6] = [15.0, 12.0, 10.0, 8.0, 6.0, 4.0];
-        const TEAM_POINTS: [f32; 2] = [10.0, 5.0];
-        const TEAM_CS_POINTS: [f32; 3] = [20.0, 16.0, 12.0];
-
-        for indiv in indiv.iter_mut() {
-            let copy = indiv.clone();
-            for (i, group) in indiv_ties
-                .iter()
-                .enumerate()
-                .take(std::cmp::min(indiv_ties.len(), 6))
-            {
-                if group.contains(&copy) {
-                    let mut sum = 0.0;
-                    for points in INDIV_POINTS
-                        .iter()
-                        .take(std::cmp::min(i + group.len(), 6))
-                        .skip(i)
-                    {
-                        sum += points;
-                    }
-                    indiv.points = sum / group.len() as f32;
-                }
-            }
-            let mut found = false;
-            for result in individual_results.iter_mut() {
-                if result.name == indiv.name {
-                    found = true;
-                    result.points += indiv.points;
-                }
-            }
-            if !found {
-                individual_results.push(indiv.clone());
-            }
-        }
-
-        for mut team in team {
-            let copy = team.clone();
-            let positions = if subject != Subject::ComputerScience {
-                2
-            } else {
-                3
-            };
-            for (i, group) in team_ties
-                .iter()
-                .enumerate()
-                .take(std::cmp::min(team_ties.len(), positions))
-            {
-                if group.contains(&copy) {
-                    let mut sum = 0.0;
-                    for ii in i..std::cmp::min(i + group.len(), positions) {
-                        if positions == 2 {
-                            sum += TEAM_POINTS[ii];
-                        } else {
-                            sum += TEAM_CS_POINTS[ii];
-                        }
-                    }
-                    team.points = sum / group.len() as f32;
-                }
-            }
-            let mut found = false;
-            for result in team_results.iter_mut() {
-                if result.school == team.school {
-                    found = true;
-                    result.points += team.points;
-                }
-            }
-            if !found {
-                team_results.push(team);
-            }
-        }
+        };
+        sources.append(&mut results.sources);
+        events.push(Event::score(subject, results.individuals, results.teams));
 
         if fields.district.is_some() {
             use std::{thread, time};
 
-            println!("Pausing to (hopefully) prevent rate limiting");
+            eprintln!("Pausing to (hopefully) prevent rate limiting");
             let second = time::Duration::from_millis(500);
 
             thread::sleep(second);
         }
     }
-    for (index, indiv) in individual_results.iter().enumerate() {
-        if index > 25 {
-            break;
-        }
-        println!("{}: {} points", indiv.name.clone(), indiv.points);
-    }
-    Some((individual_results, team_results))
+    Some(sweepstakes::totals(events, matching, sources))
 }
 
-pub fn highscores(request_fields: RequestFields, conferences: Vec<u8>, cli: Cli) {
-    let mute = cli.mute;
+/// Every season a subject's scores can be compared across
+pub fn years(subject: &Subject, region: bool) -> RangeInclusive<u16> {
     let current_year: u16 = chrono::Utc::now().year() as u16;
-    let subject = request_fields.subject;
+    match subject {
+        // The UIL CS test changed scales between region 2004 and state 2004
+        Subject::ComputerScience if region => 2005..=current_year,
+        Subject::ComputerApplications => 2004..=2024,
+        _ => 2004..=current_year,
+    }
+}
+
+/// Scrapes every year since 2004, keeping every result so the best can be shown
+pub fn highscores(request_fields: RequestFields, conferences: Vec<u8>, cli: &Cli) -> ResultSet {
     let individual_results = Arc::new(Mutex::new(Vec::new()));
     let team_results = Arc::new(Mutex::new(Vec::new()));
+    let mut sources = Vec::new();
+
+    let range = years(&request_fields.subject, request_fields.region.is_some());
+    each_year(request_fields, conferences, range, cli, |_, mut results| {
+        sources.append(&mut results.sources);
+        let (mut indiv, mut team) = (results.individuals, results.teams);
+        if !indiv.is_empty() {
+            indiv.sort_by(|a, b| {
+                let a_score = a.score;
+                let b_score = b.score;
+                b_score.cmp(&a_score)
+            });
 
-    let cs_year = if request_fields.region.is_some() {
-        2005
-    } else {
-        2004
-    };
+            individual_results.lock().unwrap().append(&mut indiv);
+        }
 
-    let range = match subject {
-        // The UIL CS test changed scales between region 2004 and state 2004
-        Subject::ComputerScience => cs_year..=current_year,
-        Subject::ComputerApplications => 2004..=2024,
-        _ => 2004..=current_year,
-    };
+        if !team.is_empty() {
+            team.sort_by(|a, b| {
+                let a_score = a.score;
+                let b_score = b.score;
+                b_score.cmp(&a_score)
+            });
+
+            team_results.lock().unwrap().append(&mut team);
+        }
+    });
+
+    let individuals = individual_results.lock().unwrap().clone();
+    let teams = team_results.lock().unwrap().clone();
+    ResultSet {
+        individuals,
+        teams,
+        sources,
+        events: Vec::new(),
+    }
+}
+
+/// Scrapes the same meets for each year in `range`, pausing every so often to avoid
+/// rate limiting, and hands each year's results to `each`
+pub fn each_year(
+    request_fields: RequestFields,
+    conferences: Vec<u8>,
+    range: RangeInclusive<u16>,
+    cli: &Cli,
+    mut each: impl FnMut(u16, ResultSet),
+) {
+    let mute = cli.mute;
+    let matching = cli.matching();
+    let subject = request_fields.subject;
 
     // Used to stop rate limiting more efficiently
     let mut count = 0;
@@ -346,13 +307,8 @@ pub fn highscores(request_fields: RequestFields, conferences: Vec<u8>, cli: Cli)
             subject: subject.clone(),
             year,
         };
-        let conferences = if year > 2014 {
-            conferences.clone()
-        } else {
-            let mut new_conf = conferences.clone();
-            new_conf.pop_if(|x| *x == 6);
-            new_conf
-        };
+        let mut conferences = conferences.clone();
+        conference::retain_existing(year, &mut conferences);
 
         count += conferences.len()
             * if fields.district.is_some() {
@@ -368,44 +324,14 @@ pub fn highscores(request_fields: RequestFields, conferences: Vec<u8>, cli: Cli)
             };
 
         let results = match subject {
-            Subject::Rankings => overall::rankings(fields.clone(), conferences.clone(), mute),
+            Subject::Rankings => {
+                overall::rankings(fields.clone(), conferences.clone(), mute, matching)
+            }
             _ => scrape_subject(fields.clone(), conferences.clone(), mute),
         };
 
-        if results.is_some() {
-            let (mut indiv, mut team) = results.unwrap();
-
-            if !indiv.is_empty() {
-                indiv.sort_by(|a, b| {
-                    let a_score = a.score;
-                    let b_score = b.score;
-                    b_score.cmp(&a_score)
-                });
-
-                let year_str = year.to_string();
-
-                indiv.iter_mut().for_each(|indiv| {
-                    indiv.school = format!("{year_str} - {}", indiv.school);
-                });
-
-                individual_results.lock().unwrap().append(&mut indiv);
-            }
-
-            if !team.is_empty() {
-                team.sort_by(|a, b| {
-                    let a_score = a.score;
-                    let b_score = b.score;
-                    b_score.cmp(&a_score)
-                });
-
-                let year_str = year.to_string();
-
-                team.iter_mut().for_each(|team| {
-                    team.school = format!("{year_str} - {}", team.school);
-                });
-
-                team_results.lock().unwrap().append(&mut team);
-            }
+        if let Some(results) = results {
+            each(year, results);
         }
 
         if count <= 20 {
@@ -415,29 +341,37 @@ pub fn highscores(request_fields: RequestFields, conferences: Vec<u8>, cli: Cli)
         if request_fields.region.is_some() {
             use std::{thread, time};
 
-            println!("Pausing to (hopefully) prevent rate limiting");
+            eprintln!("Pausing to (hopefully) prevent rate limiting");
             let second = time::Duration::from_millis(1000);
 
             thread::sleep(second);
         } else if request_fields.district.is_some() {
             use std::{thread, time};
 
-            println!("Pausing to (hopefully) prevent rate limiting");
+            eprintln!("Pausing to (hopefully) prevent rate limiting");
             let second = time::Duration::from_millis(2000);
 
             thread::sleep(second);
         } else {
             use std::{thread, time};
 
-            println!("Pausing to (hopefully) prevent rate limiting");
+            eprintln!("Pausing to (hopefully) prevent rate limiting");
             let second = time::Duration::from_millis(1000);
 
             thread::sleep(second);
         }
     }
+}
+
+pub fn display_highscores(
+    subject: &Subject,
+    mut individuals: Vec<Individual>,
+    mut teams: Vec<Team>,
+    cli: &Cli,
+) {
     println!("{} Individual Results: ", subject.to_string());
-    {
-        let mut results = individual_results.lock().unwrap();
+    if !individuals.is_empty() {
+        let results = &mut individuals;
 
         results.sort_by(|a, b| {
             let a_score = a.score;
@@ -445,17 +379,14 @@ pub fn highscores(request_fields: RequestFields, conferences: Vec<u8>, cli: Cli)
             if a_score != b_score {
                 b_score.cmp(&a_score)
             } else {
-                let a_year = &a.school[0..4];
-                let b_year = &b.school[0..4];
-
-                a_year.cmp(b_year)
+                a.year.cmp(&b.year)
             }
         });
 
         let top_score = results.first().unwrap().score;
 
         let mut longest_name_len = 0;
-        let score_len = top_score.checked_ilog10().unwrap_or(0) as usize + 1;
+        let score_len = top_score.to_string().len();
 
         results.iter().for_each(|indiv| {
             longest_name_len = std::cmp::max(longest_name_len, indiv.name.len());
@@ -465,22 +396,14 @@ pub fn highscores(request_fields: RequestFields, conferences: Vec<u8>, cli: Cli)
 
         let indiv_positions = cli.individual_positions.unwrap_or(10);
         if indiv_positions != 0 {
-            results_copy.resize(std::cmp::max(indiv_positions, 1), Individual::default());
+            results_copy.truncate(indiv_positions);
         }
 
         for indiv in results_copy.iter() {
-            let conference_str: ColoredString = match indiv.conference {
-                1 => "1A".white(),
-                2 => "2A".yellow(),
-                3 => "3A".bright_blue(),
-                4 => "4A".green(),
-                5 => "5A".red(),
-                6 => "6A".magenta(),
-                _ => "".into(),
-            };
+            let conference_str: ColoredString = theme().conference(indiv.year, indiv.conference);
             let base: ColoredString = format!(
-                "{:longest_name_len$} => {:>score_len$} ({conference_str} {})",
-                indiv.name, indiv.score, indiv.school,
+                "{:longest_name_len$} => {:>score_len$} ({conference_str} {} - {})",
+                indiv.name, indiv.score, indiv.year, indiv.school,
             )
             .into();
 
@@ -489,24 +412,17 @@ pub fn highscores(request_fields: RequestFields, conferences: Vec<u8>, cli: Cli)
         println!();
 
         // NOTE: TODO
-        if subject == Subject::Science {
+        if *subject == Subject::Science {
             results.iter_mut().for_each(|indiv| {
-                indiv.score = indiv.get_biology().unwrap_or(-120);
+                indiv.score = indiv.get_biology().unwrap_or(Score::from(-120));
             });
-            results.sort_by(|a, b| a.score.cmp(&b.score));
+            results.sort_by_key(|a| a.score);
             for indiv in results.iter() {
-                let conference_str: ColoredString = match indiv.conference {
-                    1 => "1A".white(),
-                    2 => "2A".yellow(),
-                    3 => "3A".bright_blue(),
-                    4 => "4A".green(),
-                    5 => "5A".red(),
-                    6 => "6A".magenta(),
-                    _ => "".into(),
-                };
+                let conference_str: ColoredString =
+                    theme().conference(indiv.year, indiv.conference);
                 let base: ColoredString = format!(
-                    "{:longest_name_len$} => {:>score_len$} ({conference_str} {})",
-                    indiv.name, indiv.score, indiv.school,
+                    "{:longest_name_len$} => {:>score_len$} ({conference_str} {} - {})",
+                    indiv.name, indiv.score, indiv.year, indiv.school,
                 )
                 .into();
 
@@ -516,8 +432,8 @@ pub fn highscores(request_fields: RequestFields, conferences: Vec<u8>, cli: Cli)
     }
 
     println!("{} Team Results: ", subject.to_string());
-    {
-        let mut results = team_results.lock().unwrap();
+    if !teams.is_empty() {
+        let results = &mut teams;
 
         results.sort_by(|a, b| {
             let a_score = a.score;
@@ -525,40 +441,28 @@ pub fn highscores(request_fields: RequestFields, conferences: Vec<u8>, cli: Cli)
             if a_score != b_score {
                 b_score.cmp(&a_score)
             } else {
-                let a_year = &a.school[0..4];
-                let b_year = &b.school[0..4];
-                a_year.cmp(b_year)
+                a.year.cmp(&b.year)
             }
         });
 
         let top_score = results.first().unwrap().score;
         let team_positions = cli.team_positions.unwrap_or(10);
         if team_positions != 0 {
-            results.resize(std::cmp::max(team_positions, 1), Team::default());
+            results.truncate(team_positions);
         }
 
         let mut longest_name_len = 0;
-        let score_len = top_score.checked_ilog10().unwrap_or(0) as usize + 1;
+        let score_len = top_score.to_string().len();
 
         results.iter().for_each(|team| {
-            longest_name_len = std::cmp::max(longest_name_len, team.school[7..].len());
+            longest_name_len = std::cmp::max(longest_name_len, team.school.len());
         });
 
         for team in results.iter() {
-            let conference_str: ColoredString = match team.conference {
-                1 => "1A".white(),
-                2 => "2A".yellow(),
-                3 => "3A".bright_blue(),
-                4 => "4A".green(),
-                5 => "5A".red(),
-                6 => "6A".magenta(),
-                _ => "".into(),
-            };
+            let conference_str: ColoredString = theme().conference(team.year, team.conference);
             let base: ColoredString = format!(
                 "{:longest_name_len$} => {:>score_len$} ({conference_str} {})",
-                &team.school[7..],
-                team.score,
-                &team.school[0..4],
+                team.school, team.score, team.year,
             )
             .into();
 
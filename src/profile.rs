@@ -0,0 +1,337 @@
+use chrono::Datelike;
+use colored::{ColoredString, Colorize};
+
+use crate::{
+    advance::{AdvanceTypeIndividual, AdvanceTypeTeam},
+    cli::Cli,
+    competitor::normalize_school,
+    conference,
+    individual::Individual,
+    output::ordinal,
+    request::{Level, RequestFields, Subject},
+    score::Score,
+    scrape_meet,
+    sweepstakes::Event,
+    team::Team,
+    theme::theme,
+};
+
+/// How many of a school's individuals are shown for each meet, besides anyone who advanced
+const TOP_INDIVIDUALS: usize = 3;
+
+/// How the school did at one meet of one event
+struct Meet {
+    subject: Subject,
+    level: Level,
+    team: Option<(usize, Team)>,
+    individuals: Vec<(usize, Individual)>,
+    /// Sweepstakes points the school earned here
+    points: Score,
+}
+
+impl Meet {
+    fn advanced(&self) -> bool {
+        self.team
+            .as_ref()
+            .is_some_and(|(_, team)| team.advance.is_some())
+            || self
+                .individuals
+                .iter()
+                .any(|(_, individual)| individual.advance.is_some())
+    }
+}
+
+/// Scrapes every event of one season for a school, following it from district
+/// up to region and state for as long as anyone advanced
+pub fn run(name: &str, year: Option<u16>, conferences: Option<String>, cli: &Cli) {
+    let year = year.unwrap_or_else(|| chrono::Utc::now().year().try_into().unwrap_or(2004));
+    let mut conferences = match conferences {
+        Some(conferences) => match RequestFields::parse_range(conferences.clone()) {
+            Some(conferences) => conferences,
+            None => {
+                eprintln!("{}", format!("Unknown conference {conferences}").red());
+                return;
+            }
+        },
+        None => conference::all_codes(),
+    };
+
+    let wanted = normalize_school(name);
+    let is_ours = |school: &str| normalize_school(school) == wanted;
+
+    // Once the school turns up its district is known, so later events only scrape that one
+    let mut district: Option<u8> = None;
+    let mut school = String::from(name);
+    let mut meets: Vec<Meet> = Vec::new();
+    for subject in Subject::events(year) {
+        let mut level = Level::District(district.unwrap_or(0));
+        while let Some(report) = scrape_meet(&subject, year, level, conferences.clone(), cli) {
+            let scored = Event::score(
+                subject.clone(),
+                report.individuals.clone(),
+                report.teams.clone(),
+            );
+            let points = scored
+                .individuals
+                .iter()
+                .filter(|individual| is_ours(&individual.school))
+                .map(|individual| individual.points)
+                .chain(
+                    scored
+                        .teams
+                        .iter()
+                        .filter(|team| is_ours(&team.school))
+                        .map(|team| team.points),
+                )
+                .fold(Score::default(), |total, points| total + points);
+
+            let team = report
+                .teams
+                .iter()
+                .find(|team| is_ours(&team.school))
+                .map(|team| (report.team_place(team), team.clone()));
+            let mut individuals: Vec<(usize, Individual)> = report
+                .individuals
+                .iter()
+                .filter(|individual| is_ours(&individual.school))
+                .map(|individual| (report.individual_place(individual), individual.clone()))
+                .collect();
+            individuals.sort_by_key(|(place, _)| *place);
+
+            let Some(found) = team
+                .as_ref()
+                .map(|(_, team)| (team.conference, team.district, team.school.clone()))
+                .or_else(|| {
+                    individuals.first().map(|(_, individual)| {
+                        (
+                            individual.conference,
+                            individual.district,
+                            individual.school.clone(),
+                        )
+                    })
+                })
+            else {
+                break;
+            };
+            let (found_conference, found_district, found_school) = found;
+            if district.is_none() && found_district.is_some() {
+                district = found_district;
+                conferences = vec![found_conference];
+                school = found_school;
+            }
+
+            let meet = Meet {
+                subject: subject.clone(),
+                level: match level {
+                    Level::District(_) => Level::District(found_district.unwrap_or(0)),
+                    level => level,
+                },
+                team,
+                individuals,
+                points,
+            };
+            let advanced = meet.advanced();
+            meets.push(meet);
+
+            match level.next(found_district.or(district), year, found_conference) {
+                Some(next) if advanced => level = next,
+                _ => break,
+            }
+        }
+    }
+
+    if meets.is_empty() {
+        eprintln!("{}", format!("Couldn't find {name} in {year}").red());
+        return;
+    }
+    print(&school, year, &conferences, &meets, cli);
+}
+
+fn print(school: &str, year: u16, conferences: &[u8], meets: &[Meet], cli: &Cli) {
+    let theme = theme();
+    let district = meets.iter().find_map(|meet| match meet.level {
+        Level::District(district) => Some(district),
+        _ => None,
+    });
+    let conference = conferences.first().copied().unwrap_or_default();
+
+    let mut levels: Vec<Level> = meets.iter().map(|meet| meet.level).collect();
+    levels.sort();
+    levels.dedup();
+    let total = meets
+        .iter()
+        .fold(Score::default(), |total, meet| total + meet.points);
+
+    if cli.plain {
+        println!(
+            "{school}; year: {year}; conference: {}; district: {}",
+            conference::label(year, conference),
+            district
+                .map(|district| district.to_string())
+                .unwrap_or_default()
+        );
+        for meet in meets {
+            let mut fields = vec![meet.subject.to_string().to_string(), meet.level.to_string()];
+            if let Some((place, team)) = &meet.team {
+                fields.push(format!("team place: {}", ordinal(*place)));
+                fields.push(format!("team score: {}", team.score));
+                if let Some(advance) = &team.advance {
+                    fields.push(format!(
+                        "team advances: {}",
+                        match advance {
+                            AdvanceTypeTeam::Advance => "advance",
+                            AdvanceTypeTeam::Alternate => "alternate",
+                        }
+                    ));
+                }
+            }
+            fields.push(format!("points: {}", meet.points));
+            println!("{}", fields.join("; "));
+            for (place, individual) in shown(meet) {
+                let mut fields = vec![
+                    format!("  {}", individual.name),
+                    format!("place: {}", ordinal(*place)),
+                    format!("score: {}", individual.score),
+                ];
+                if let Some(advance) = &individual.advance {
+                    fields.push(format!("advances: {}", advance_label(advance)));
+                }
+                println!("{}", fields.join("; "));
+            }
+        }
+        for level in levels.iter() {
+            let points = level_points(meets, *level);
+            println!("sweepstakes; {level}; points: {points}");
+        }
+        println!("sweepstakes; total; points: {total}");
+        return;
+    }
+
+    print!(
+        "{} {year} {}",
+        school.bold(),
+        theme.conference(year, conference)
+    );
+    match district {
+        Some(district) => println!(" District {district}"),
+        None => println!(),
+    }
+
+    let subject_length = meets
+        .iter()
+        .map(|meet| meet.subject.to_string().len())
+        .max()
+        .unwrap_or(0);
+    let level_length = meets
+        .iter()
+        .map(|meet| meet.level.to_string().len())
+        .max()
+        .unwrap_or(0);
+    let mut last: Option<&Subject> = None;
+    for meet in meets {
+        let subject = if last == Some(&meet.subject) {
+            ""
+        } else {
+            meet.subject.to_string()
+        };
+        last = Some(&meet.subject);
+        let team = match &meet.team {
+            Some((place, team)) => {
+                let advance: ColoredString = match &team.advance {
+                    Some(advance) => {
+                        let (label, color) = theme.team_badge(advance);
+                        label.color(color)
+                    }
+                    None => "".into(),
+                };
+                format!(
+                    "team {} {} {advance}",
+                    theme.medal(format!("{:>4}", ordinal(*place)), *place),
+                    team.score
+                )
+            }
+            None => String::from("no team"),
+        };
+        println!(
+            "  {subject:subject_length$}  {:level_length$}  {team}  {} pts",
+            meet.level.to_string(),
+            meet.points
+        );
+        for (place, individual) in shown(meet) {
+            let advance: ColoredString = match &individual.advance {
+                Some(advance) => {
+                    let (label, color) = theme.individual_badge(advance);
+                    label.color(color)
+                }
+                None => "".into(),
+            };
+            println!(
+                "  {:subject_length$}  {:level_length$}       {} {} {} {advance}",
+                "",
+                "",
+                theme.medal(format!("{:>4}", ordinal(*place)), *place),
+                individual.name,
+                individual.score
+            );
+        }
+    }
+
+    println!();
+    println!("{}", "Advanced:".bold());
+    let mut anyone = false;
+    for level in levels.iter().skip(1) {
+        // Each competitor once, with every event they made it to
+        let mut names: Vec<(String, Vec<&str>)> = Vec::new();
+        for meet in meets.iter().filter(|meet| meet.level == *level) {
+            let team = meet.team.as_ref().map(|_| String::from("Team"));
+            let individuals = meet
+                .individuals
+                .iter()
+                .map(|(_, individual)| individual.name.clone());
+            for name in team.into_iter().chain(individuals) {
+                match names.iter_mut().find(|(other, _)| *other == name) {
+                    Some((_, subjects)) => subjects.push(meet.subject.to_string()),
+                    None => names.push((name, vec![meet.subject.to_string()])),
+                }
+            }
+        }
+        for (name, subjects) in names.iter() {
+            anyone = true;
+            println!("  {level}: {name} ({})", subjects.join(", "));
+        }
+    }
+    if !anyone {
+        println!("  Nobody");
+    }
+
+    println!();
+    println!("{}", "Sweepstakes:".bold());
+    for level in levels.iter() {
+        println!("  {level}: {} pts", level_points(meets, *level));
+    }
+    println!("  Total: {}", total.to_string().bold());
+}
+
+/// The school's best individuals at a meet, and anyone else who advanced
+fn shown(meet: &Meet) -> impl Iterator<Item = &(usize, Individual)> {
+    meet.individuals
+        .iter()
+        .enumerate()
+        .filter(|(index, (_, individual))| *index < TOP_INDIVIDUALS || individual.advance.is_some())
+        .map(|(_, shown)| shown)
+}
+
+fn level_points(meets: &[Meet], level: Level) -> Score {
+    meets
+        .iter()
+        .filter(|meet| meet.level == level)
+        .fold(Score::default(), |total, meet| total + meet.points)
+}
+
+const fn advance_label(advance: &AdvanceTypeIndividual) -> &'static str {
+    match advance {
+        AdvanceTypeIndividual::Indiv => "individual",
+        AdvanceTypeIndividual::Team => "team",
+        AdvanceTypeIndividual::Wild => "wildcard",
+    }
+}
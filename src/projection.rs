@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use crate::{
+    advance::{AdvanceTypeIndividual, AdvanceTypeTeam},
+    individual::Individual,
+    request::Level,
+    results::ResultSet,
+    score::Score,
+    team::Team,
+};
+
+/// How many individuals advance by placing at a meet
+const ADVANCING_PLACES: usize = 3;
+
+/// Printed with every projection, since a meet is never actually held with these scores
+pub const CAVEAT: &str = "Projected from lower-level results: each score comes from a different test, so this is only an estimate of the meet";
+
+/// Moves a row to the meet it advanced to, keeping its score
+fn promote(level: Level, district: &mut Option<u8>, region: &mut Option<u8>) {
+    match level {
+        Level::Region(next) => {
+            *district = None;
+            *region = Some(next);
+        }
+        _ => {
+            *district = None;
+            *region = None;
+        }
+    }
+}
+
+/// Simulates the next meet up: everyone who advanced is pooled by the meet they
+/// would compete at, then placed and advanced again under the same rules
+pub fn project(results: ResultSet) -> ResultSet {
+    let mut individuals: Vec<Individual> = results
+        .individuals
+        .into_iter()
+        .filter(|individual| individual.advance.is_some())
+        .filter_map(|mut individual| {
+            let level = individual.level.next(
+                individual.district,
+                individual.year,
+                individual.conference,
+            )?;
+            promote(level, &mut individual.district, &mut individual.region);
+            individual.level = level;
+            individual.advance = None;
+            individual.points = Score::default();
+            Some(individual)
+        })
+        .collect();
+
+    let mut teams: Vec<Team> = results
+        .teams
+        .into_iter()
+        .filter(|team| team.advance.is_some())
+        .filter_map(|mut team| {
+            let level = team.level.next(team.district, team.year, team.conference)?;
+            promote(level, &mut team.district, &mut team.region);
+            team.level = level;
+            team.advance = None;
+            team.points = Score::default();
+            Some(team)
+        })
+        .collect();
+
+    // Nobody advances from state, so a projected state meet only places everyone
+    let mut meets: HashMap<(u8, Level), Vec<Score>> = HashMap::new();
+    for individual in individuals.iter() {
+        meets
+            .entry((individual.conference, individual.level))
+            .or_default()
+            .push(individual.score);
+    }
+    for individual in individuals.iter_mut() {
+        if individual.level == Level::State {
+            continue;
+        }
+        let scores = &meets[&(individual.conference, individual.level)];
+        let ahead = scores
+            .iter()
+            .filter(|score| **score > individual.score)
+            .count();
+        if ahead < ADVANCING_PLACES {
+            individual.advance = Some(AdvanceTypeIndividual::Indiv);
+        }
+    }
+
+    let regional: Vec<Team> = teams
+        .iter()
+        .filter(|team| team.level != Level::State)
+        .cloned()
+        .collect();
+    let advancing = Team::get_advancing(regional);
+    for team in teams.iter_mut() {
+        if !advancing.contains(team) {
+            continue;
+        }
+        let winner = advancing.iter().all(|other| {
+            other.conference != team.conference
+                || other.level != team.level
+                || other.score <= team.score
+        });
+        team.advance = Some(if winner {
+            AdvanceTypeTeam::Advance
+        } else {
+            AdvanceTypeTeam::Alternate
+        });
+    }
+
+    ResultSet {
+        individuals,
+        teams,
+        sources: results.sources,
+        events: Vec::new(),
+    }
+}
@@ -2,7 +2,12 @@ use colored::Colorize;
 use minreq::Response;
 use scraper::{Html, Selector};
 
-use crate::{individual::Individual, team::Team};
+use crate::{
+    alignment,
+    individual::Individual,
+    results::{ResultSet, Source},
+    team::Team,
+};
 
 #[derive(Clone, Debug)]
 pub struct RequestFields {
@@ -14,7 +19,50 @@ pub struct RequestFields {
     pub year: u16,
 }
 
+/// The meet that a set of results was posted for
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Level {
+    District(u8),
+    Region(u8),
+    State,
+}
+
+impl Level {
+    /// The meet competitors who advanced from this one go on to, if there is one
+    pub fn next(self, district: Option<u8>, year: u16, conference: u8) -> Option<Self> {
+        match self {
+            Self::District(_) => district_as_region(district, year, conference).map(Self::Region),
+            Self::Region(_) => Some(Self::State),
+            Self::State => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::District(district) => write!(f, "District {district}"),
+            Self::Region(region) => write!(f, "Region {region}"),
+            Self::State => write!(f, "State"),
+        }
+    }
+}
+
 impl RequestFields {
+    pub const fn level(&self) -> Option<Level> {
+        if self.state {
+            Some(Level::State)
+        } else if let Some(region) = self.region {
+            Some(Level::Region(region))
+        } else if let Some(district) = self.district {
+            Some(Level::District(district))
+        } else {
+            None
+        }
+    }
+
+    /// Parses conferences like "4", "4A", "1-4" or "1A,4A". Whether the
+    /// conferences existed in a given year is checked separately.
     pub fn parse_range(mut string: String) -> Option<Vec<u8>> {
         if string.is_empty() {
             return None;
@@ -22,22 +70,24 @@ impl RequestFields {
         string = string.to_lowercase();
         if string.contains(',') {
             let mut split = string.split(',');
-            let left_num = split.next().unwrap().parse::<u8>().ok()?;
-            let right_num = split.next().unwrap().parse::<u8>().ok()?;
+            let mut parse = || {
+                let mut number = split.next()?.to_string();
+                number.retain(|c| c.is_ascii_digit());
+                number.parse::<u8>().ok().filter(|number| *number >= 1)
+            };
+            let left_num = parse()?;
+            let right_num = parse()?;
             let vec = vec![left_num, right_num];
             return Some(vec);
         }
         string.retain(|c| c.is_ascii_digit());
         let bytes = string.as_bytes();
         // char to u8
-        let left_digit = bytes[0] - 48;
+        let left_digit = bytes.first()? - 48;
         if bytes.len() == 1 {
             if left_digit < 1 {
                 return None;
             }
-            if left_digit > 6 {
-                return None;
-            }
 
             let vec = vec![left_digit];
             return Some(vec);
@@ -49,9 +99,6 @@ impl RequestFields {
         if start < 1 {
             return None;
         }
-        if end > 6 {
-            return None;
-        }
 
         let mut vec = Vec::new();
         for i in start..=end {
@@ -59,18 +106,25 @@ impl RequestFields {
         }
         Some(vec)
     }
+
+    /// Parses seasons like "2023" or "2021-2024"
+    pub fn parse_years(string: &str) -> Option<std::ops::RangeInclusive<u16>> {
+        let (start, end) = string.split_once('-').unwrap_or((string, string));
+        let start: u16 = start.trim().parse().ok()?;
+        let end: u16 = end.trim().parse().ok()?;
+        Some(std::cmp::min(start, end)..=std::cmp::max(start, end))
+    }
+
     fn get_district(&self) -> String {
-        if self.district.is_none() {
-            String::new()
-        } else {
-            self.district.unwrap().to_string()
+        match self.district {
+            Some(district) => district.to_string(),
+            None => String::new(),
         }
     }
     fn get_region(&self) -> String {
-        if self.region.is_none() {
-            String::new()
-        } else {
-            self.region.unwrap().to_string()
+        match self.region {
+            Some(region) => region.to_string(),
+            None => String::new(),
         }
     }
     fn get_state(&self) -> String {
@@ -82,20 +136,24 @@ impl RequestFields {
     }
 }
 
-pub fn request(fields: RequestFields) -> Option<String> {
+pub fn url(fields: &RequestFields) -> String {
     let district = fields.get_district();
     let region = fields.get_region();
     let state = fields.get_state();
     let subject: i8 = fields.subject.to_i8();
     let conference = fields.conference;
-    let url: String = if fields.year > 2022 {
+    if fields.year > 2022 {
         let year = fields.year - 2008;
         format!(
             "https://postings.speechwire.com/r-uil-academics.php?groupingid={subject}&Submit=View+postings&region={region}&district={district}&state={state}&conference={conference}&seasonid={year}"
         )
     } else {
-        old_school(fields)
-    };
+        old_school(fields.clone())
+    }
+}
+
+pub fn request(fields: RequestFields) -> Option<String> {
+    let url = url(&fields);
     let response: Response = minreq::get(url).with_timeout(1000).send().ok()?;
 
     if response.status_code >= 400 {
@@ -113,11 +171,19 @@ pub fn request(fields: RequestFields) -> Option<String> {
     Some(response.as_str().ok()?.to_string())
 }
 
-pub fn perform_scrape(fields: RequestFields) -> Option<(Vec<Individual>, Vec<Team>)> {
+pub fn perform_scrape(fields: RequestFields) -> Option<ResultSet> {
     let mut individual_results: Vec<Individual> = Vec::new();
     let mut team_results: Vec<Team> = Vec::new();
 
     let request = request(fields.clone())?;
+    let source = Source {
+        year: fields.year,
+        subject: fields.subject.clone(),
+        level: fields.level()?,
+        conference: fields.conference,
+        url: url(&fields),
+        fetched: chrono::Utc::now(),
+    };
 
     if fields.year > 2022 {
         let document = Html::parse_document(request.as_str());
@@ -135,7 +201,11 @@ pub fn perform_scrape(fields: RequestFields) -> Option<(Vec<Individual>, Vec<Tea
 
         team_results.append(&mut teams);
 
-        Some((individual_results, team_results))
+        Some(ResultSet::from_source(
+            individual_results,
+            team_results,
+            source,
+        ))
     } else {
         let document = Html::parse_document(request.as_str());
         let table_selector = Selector::parse("table").ok()?;
@@ -156,12 +226,16 @@ pub fn perform_scrape(fields: RequestFields) -> Option<(Vec<Individual>, Vec<Tea
 
         team_results.append(&mut teams);
 
-        Some((individual_results, team_results))
+        Some(ResultSet::from_source(
+            individual_results,
+            team_results,
+            source,
+        ))
     }
 }
 
 #[allow(dead_code)]
-#[derive(Clone, PartialEq, Eq, Debug)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Debug)]
 pub enum Subject {
     Accounting,
     // NOTE: computer applications isn't fully supported
@@ -200,6 +274,27 @@ impl Subject {
         }
     }
 
+    /// Every event that counts towards sweepstakes in `year`
+    pub fn events(year: u16) -> Vec<Self> {
+        let mut events = vec![
+            Self::Accounting,
+            Self::ComputerApplications,
+            Self::CurrentEvents,
+            Self::ComputerScience,
+            Self::Calculator,
+            Self::Spelling,
+            Self::Science,
+            Self::SocialStudies,
+            Self::Mathematics,
+            Self::NumberSense,
+        ];
+        if year > 2024 {
+            // Computer Apps is discontinued
+            events.retain(|event| *event != Self::ComputerApplications);
+        }
+        events
+    }
+
     pub fn from_str(string: &str) -> Option<Self> {
         match string.to_lowercase().as_str() {
             "accounting" => Some(Self::Accounting),
@@ -230,7 +325,8 @@ impl Subject {
             Self::SocialStudies => "Social Studies",
             Self::Mathematics => "Mathematics",
             Self::NumberSense => "Number Sense",
-            _ => "",
+            Self::Sweepstakes => "Sweepstakes",
+            Self::Rankings => "Rankings",
         }
     }
 
@@ -257,21 +353,9 @@ impl Subject {
     }
 }
 
-pub fn district_as_region(district: Option<u8>) -> Option<u8> {
-    district?;
-    let region = match district.unwrap() {
-        1..=8 => 1,
-        9..=16 => 2,
-        17..=24 => 3,
-        25..=32 => 4,
-        _ => 0,
-    };
-
-    if region == 0 {
-        return None;
-    }
-
-    Some(region)
+/// The region a district belongs to, using the alignment for that year and conference
+pub fn district_as_region(district: Option<u8>, year: u16, conference: u8) -> Option<u8> {
+    alignment::alignment(year, conference).region_of(district?)
 }
 
 #[allow(unreachable_code)]
@@ -287,10 +371,10 @@ pub fn old_school(fields: RequestFields) -> String {
 
     let base = "https://utdirect.utexas.edu/nlogon/uil/vlcp_pub_arch.WBX?".to_string();
 
-    let number = if fields.district.is_some() {
-        fields.district.unwrap().to_string()
-    } else if fields.region.is_some() {
-        fields.region.unwrap().to_string()
+    let number = if let Some(district) = fields.district {
+        district.to_string()
+    } else if let Some(region) = fields.region {
+        region.to_string()
     } else {
         "".to_string()
     };
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use serde::{Serialize, Serializer};
+
+use crate::{
+    individual::Individual,
+    request::{Level, Subject},
+    team::Team,
+};
+
+/// Where a row of results was scraped from
+#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+pub struct Source {
+    pub year: u16,
+    #[serde(serialize_with = "serialize_subject")]
+    pub subject: Subject,
+    #[serde(serialize_with = "serialize_level")]
+    pub level: Level,
+    pub conference: u8,
+    pub url: String,
+    pub fetched: DateTime<Utc>,
+}
+
+/// Individual and team results, along with every page they were scraped from.
+/// Each row points to its page through its `source`; combined rows (rankings,
+/// sweepstakes) keep the source of the first page they were found on.
+#[derive(Clone, Default, Debug)]
+pub struct ResultSet {
+    pub individuals: Vec<Individual>,
+    pub teams: Vec<Team>,
+    pub sources: Vec<Arc<Source>>,
+    /// Each event's own results, when rows were combined across events (rankings, sweepstakes)
+    pub events: Vec<(Subject, ResultSet)>,
+}
+
+impl ResultSet {
+    pub fn from_source(
+        mut individuals: Vec<Individual>,
+        mut teams: Vec<Team>,
+        source: Source,
+    ) -> Self {
+        let source = Arc::new(source);
+        for individual in individuals.iter_mut() {
+            individual.source = Some(source.clone());
+        }
+        for team in teams.iter_mut() {
+            team.source = Some(source.clone());
+        }
+        Self {
+            individuals,
+            teams,
+            sources: vec![source],
+            events: Vec::new(),
+        }
+    }
+
+    pub fn append(&mut self, other: &mut Self) {
+        self.individuals.append(&mut other.individuals);
+        self.teams.append(&mut other.teams);
+        self.sources.append(&mut other.sources);
+        self.events.append(&mut other.events);
+    }
+}
+
+fn serialize_subject<S: Serializer>(subject: &Subject, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(subject.to_string())
+}
+
+fn serialize_level<S: Serializer>(level: &Level, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(level)
+}
@@ -0,0 +1,243 @@
+use std::{collections::HashMap, fs, sync::OnceLock};
+
+const BUILT_IN_ALIASES: &str = include_str!("../data/school_aliases.txt");
+
+static ALIASES: OnceLock<SchoolAliases> = OnceLock::new();
+
+#[derive(Default, Debug)]
+pub struct SchoolAliases {
+    drop: Vec<Vec<String>>,
+    words: HashMap<String, String>,
+    schools: Vec<(String, String)>,
+    /// Normalized school key -> name to show
+    display: HashMap<String, String>,
+    /// Normalized key of an aliased spelling -> key of the school it belongs to
+    canonical: HashMap<String, String>,
+}
+
+impl SchoolAliases {
+    pub fn built_in() -> Self {
+        let mut aliases = Self::default();
+        aliases
+            .add(BUILT_IN_ALIASES)
+            .expect("Built-in school aliases are malformed");
+        aliases.build();
+        aliases
+    }
+
+    /// Adds the rules from an alias file, with later rules taking priority
+    pub fn add(&mut self, text: &str) -> Result<(), String> {
+        let mut section = "";
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                section = match &line[1..line.len() - 1] {
+                    "drop" => "drop",
+                    "words" => "words",
+                    "schools" => "schools",
+                    other => return Err(format!("line {}: unknown section [{other}]", number + 1)),
+                };
+                continue;
+            }
+
+            match section {
+                "drop" => self.drop.push(split_words(line)),
+                "words" | "schools" => {
+                    let Some((from, to)) = line.split_once('=') else {
+                        return Err(format!("line {}: expected `<from> = <to>`", number + 1));
+                    };
+                    let (from, to) = (from.trim(), to.trim());
+                    if section == "words" {
+                        self.words.insert(from.to_lowercase(), to.to_lowercase());
+                    } else {
+                        self.schools.push((from.to_string(), to.to_string()));
+                    }
+                }
+                _ => return Err(format!("line {}: rule outside of a section", number + 1)),
+            }
+        }
+        Ok(())
+    }
+
+    fn build(&mut self) {
+        self.display.clear();
+        self.canonical.clear();
+        for (from, to) in self.schools.clone() {
+            let (from_key, to_key) = (self.spelling_key(&from), self.spelling_key(&to));
+            self.display.insert(from_key.clone(), to.clone());
+            self.display.insert(to_key.clone(), to);
+            if from_key != to_key {
+                self.canonical.insert(from_key, to_key);
+            }
+        }
+    }
+
+    /// The string two school names are compared by, the same for every spelling in the alias table
+    pub fn key(&self, school: &str) -> String {
+        let key = self.spelling_key(school);
+        self.canonical.get(&key).cloned().unwrap_or(key)
+    }
+
+    /// A school name with the word rules applied, before the school table is
+    fn spelling_key(&self, school: &str) -> String {
+        let mut words: Vec<String> = Vec::new();
+        for word in split_words(school) {
+            match self.words.get(&word) {
+                Some(replacement) => words.extend(split_words(replacement)),
+                None => words.push(word),
+            }
+        }
+
+        for phrase in &self.drop {
+            if phrase.is_empty() {
+                continue;
+            }
+            let mut index = 0;
+            while index + phrase.len() <= words.len() {
+                if words[index..index + phrase.len()] == phrase[..] {
+                    words.drain(index..index + phrase.len());
+                } else {
+                    index += 1;
+                }
+            }
+        }
+
+        words.join(" ")
+    }
+
+    /// The name to show for a school, which is the same for every spelling in the alias table
+    pub fn display(&self, school: &str) -> String {
+        if let Some(name) = self.display.get(&self.key(school)) {
+            return name.clone();
+        }
+
+        let mut name = school.split_whitespace().collect::<Vec<&str>>().join(" ");
+        if let Some(stripped) = name.strip_suffix(" H S") {
+            name = format!("{stripped} HS");
+        }
+        name
+    }
+}
+
+/// Loads the built-in aliases plus the ones in `path`, if given.
+/// Has to be called before any results are parsed for the extra aliases to apply.
+pub fn load(path: Option<&str>) -> Result<(), String> {
+    let mut aliases = SchoolAliases::built_in();
+    if let Some(path) = path {
+        let text = fs::read_to_string(path).map_err(|err| format!("{path}: {err}"))?;
+        aliases.add(&text).map_err(|err| format!("{path}: {err}"))?;
+        aliases.build();
+    }
+    ALIASES
+        .set(aliases)
+        .map_err(|_| String::from("school aliases were already loaded"))
+}
+
+pub fn aliases() -> &'static SchoolAliases {
+    ALIASES.get_or_init(SchoolAliases::built_in)
+}
+
+/// Cleans up a school name as posted, using the alias table
+pub fn normalize(school: &str) -> String {
+    aliases().display(school)
+}
+
+pub fn key(school: &str) -> String {
+    aliases().key(school)
+}
+
+fn split_words(string: &str) -> Vec<String> {
+    string
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == ',' || c == '-' || c == '/')
+        .map(|word| {
+            word.chars()
+                .filter(|c| c.is_alphanumeric() || *c == '&')
+                .collect::<String>()
+        })
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_file(text: &str) -> SchoolAliases {
+        let mut aliases = SchoolAliases::built_in();
+        aliases.add(text).unwrap();
+        aliases.build();
+        aliases
+    }
+
+    #[test]
+    fn legacy_and_speechwire_spellings_are_the_same_school() {
+        let aliases = SchoolAliases::built_in();
+        assert_eq!(
+            aliases.display("Dallas Sci & Eng H S"),
+            "Dallas Sci. & Eng."
+        );
+        assert_eq!(aliases.display("Dallas Sci. & Eng."), "Dallas Sci. & Eng.");
+        assert_eq!(
+            aliases.key("Dallas Sci & Eng H S"),
+            aliases.key("Dallas Sci. & Eng.")
+        );
+        assert_eq!(
+            aliases.spelling_key("Dallas Sci & Eng H S"),
+            "dallas science & engineering"
+        );
+    }
+
+    #[test]
+    fn unaliased_schools_only_get_cleaned_up() {
+        let aliases = SchoolAliases::built_in();
+        assert_eq!(aliases.display("Plano  West H S"), "Plano West HS");
+        assert_eq!(aliases.key("Plano West High School"), "plano west");
+    }
+
+    #[test]
+    fn alias_file_rules_override_built_in_ones() {
+        let aliases =
+            with_file("[words]\nsci = scientific\n\n[schools]\nDallas Sci. & Eng. = Dallas SEM\n");
+        assert_eq!(
+            aliases.spelling_key("Dallas Sci & Eng H S"),
+            "dallas scientific & engineering"
+        );
+        assert_eq!(aliases.display("Dallas Sci & Eng H S"), "Dallas SEM");
+        assert_eq!(aliases.display("Dallas Sci. & Eng."), "Dallas SEM");
+    }
+
+    #[test]
+    fn alias_file_rules_add_to_built_in_ones() {
+        let aliases = with_file("[drop]\nmagnet\n\n[schools]\nKIPP Austin Coll = KIPP Austin\n");
+        assert_eq!(aliases.key("Foo Magnet H S"), "foo");
+        assert_eq!(
+            aliases.key("KIPP Austin Collegiate"),
+            aliases.key("KIPP Austin")
+        );
+        assert_eq!(
+            aliases.display("Dallas Sci & Eng H S"),
+            "Dallas Sci. & Eng."
+        );
+    }
+
+    #[test]
+    fn malformed_files_are_rejected() {
+        let mut aliases = SchoolAliases::default();
+        assert_eq!(
+            aliases.add("[towns]\n"),
+            Err(String::from("line 1: unknown section [towns]"))
+        );
+        assert_eq!(
+            aliases.add("# rules\nsci = science\n"),
+            Err(String::from("line 2: rule outside of a section"))
+        );
+        assert_eq!(
+            aliases.add("[words]\nsci\n"),
+            Err(String::from("line 2: expected `<from> = <to>`"))
+        );
+    }
+}
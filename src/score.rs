@@ -0,0 +1,252 @@
+use serde::{Serialize, Serializer};
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Add, AddAssign},
+};
+
+/// Thousandths per point
+const SCALE: i64 = 1000;
+/// Most digits after the decimal point that are kept
+const MAX_PRECISION: u8 = 3;
+
+/// A score or point total that keeps the precision it was posted with,
+/// so "7.5" stays 7.5 and "101.25" isn't truncated to 101.
+/// Scores compare by value only, so 7.5 and 7.50 are tied.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Score {
+    /// In thousandths of a point
+    value: i64,
+    /// Digits after the decimal point to show
+    precision: u8,
+}
+
+impl Score {
+    /// Parses a posted score like "7.5" or "-12". Digits past the third decimal
+    /// place are rounded off, half away from zero.
+    pub fn parse(string: &str) -> Option<Self> {
+        let string = string.trim();
+        let (negative, digits) = match string.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, string.strip_prefix('+').unwrap_or(string)),
+        };
+        let (whole, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if whole.is_empty() && fraction.is_empty() {
+            return None;
+        }
+        if !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let mut value = if whole.is_empty() {
+            0
+        } else {
+            whole.parse::<i64>().ok()?.checked_mul(SCALE)?
+        };
+        let precision = std::cmp::min(fraction.len(), MAX_PRECISION as usize);
+        let mut place = SCALE / 10;
+        for digit in fraction.chars().take(precision) {
+            value += (digit as i64 - '0' as i64) * place;
+            place /= 10;
+        }
+        if fraction[precision..].starts_with(['5', '6', '7', '8', '9']) {
+            value += 1;
+        }
+
+        Some(Self {
+            value: if negative { -value } else { value },
+            precision: precision as u8,
+        })
+    }
+
+    /// Splits a score evenly, e.g. points shared between tied places.
+    /// Splits that don't come out even are shown with two decimal places.
+    pub fn split(self, count: usize) -> Self {
+        let count = std::cmp::max(count, 1) as i64;
+        let value = (self.value as f64 / count as f64).round() as i64;
+        let precision = if self.value % count == 0 {
+            let needed = match value % SCALE {
+                0 => 0,
+                rest if rest % 100 == 0 => 1,
+                rest if rest % 10 == 0 => 2,
+                _ => MAX_PRECISION,
+            };
+            std::cmp::max(self.precision, needed)
+        } else {
+            std::cmp::max(self.precision, 2)
+        };
+        Self { value, precision }
+    }
+
+    /// The score as a plain number, for averages and charts
+    pub fn as_f64(self) -> f64 {
+        self.value as f64 / SCALE as f64
+    }
+
+    /// The middle of some sorted scores, splitting the difference when there are two
+    pub fn median(sorted: &[Self]) -> Option<Self> {
+        let middle = sorted.len() / 2;
+        match sorted.len() {
+            0 => None,
+            length if length % 2 == 1 => Some(sorted[middle]),
+            _ => Some((sorted[middle - 1] + sorted[middle]).split(2)),
+        }
+    }
+}
+
+impl From<i64> for Score {
+    fn from(value: i64) -> Self {
+        Self {
+            value: value * SCALE,
+            precision: 0,
+        }
+    }
+}
+
+impl PartialEq for Score {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.value.cmp(&other.value)
+    }
+}
+
+impl Hash for Score {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl Add for Score {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            value: self.value + other.value,
+            precision: std::cmp::max(self.precision, other.precision),
+        }
+    }
+}
+
+impl AddAssign for Score {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl std::iter::Sum for Score {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |total, score| total + score)
+    }
+}
+
+impl Serialize for Score {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.value % SCALE == 0 {
+            serializer.serialize_i64(self.value / SCALE)
+        } else {
+            serializer.serialize_f64(self.value as f64 / SCALE as f64)
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let divisor = 10_u64.pow((MAX_PRECISION - self.precision) as u32);
+        let unit = 10_u64.pow(self.precision as u32);
+        let scaled = (self.value.unsigned_abs() + divisor / 2) / divisor;
+        let sign = if self.value < 0 && scaled != 0 {
+            "-"
+        } else {
+            ""
+        };
+
+        let string = if self.precision == 0 {
+            format!("{sign}{scaled}")
+        } else {
+            format!(
+                "{sign}{}.{:0width$}",
+                scaled / unit,
+                scaled % unit,
+                width = self.precision as usize
+            )
+        };
+        // Lets scores be aligned with `{:>width$}` like the integers they replaced
+        f.pad(&string)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(string: &str) -> Score {
+        Score::parse(string).unwrap()
+    }
+
+    #[test]
+    fn trailing_zeros_compare_equal_but_show_as_posted() {
+        assert_eq!(parse("7.5"), parse("7.50"));
+        assert_eq!(parse("7.5").to_string(), "7.5");
+        assert_eq!(parse("7.50").to_string(), "7.50");
+    }
+
+    #[test]
+    fn keeps_two_decimal_places() {
+        assert_eq!(parse("101.25").to_string(), "101.25");
+        assert!(parse("101.25") > Score::from(101));
+    }
+
+    #[test]
+    fn rounds_past_the_third_decimal_place() {
+        assert_eq!(parse("-0.0004"), Score::default());
+        assert_eq!(parse("-0.0004").to_string(), "0.000");
+        assert_eq!(parse("1.2345").to_string(), "1.235");
+        assert_eq!(parse("0.9996").to_string(), "1.000");
+    }
+
+    #[test]
+    fn rejects_what_isnt_a_number() {
+        assert_eq!(Score::parse(""), None);
+        assert_eq!(Score::parse("."), None);
+        assert_eq!(Score::parse("12a"), None);
+    }
+
+    #[test]
+    fn uneven_splits_show_two_decimal_places() {
+        assert_eq!(Score::from(10).split(3).to_string(), "3.33");
+        assert_eq!(Score::from(10).split(2).to_string(), "5");
+        assert_eq!(Score::from(5).split(2).to_string(), "2.5");
+    }
+
+    #[test]
+    fn median_splits_the_middle_two() {
+        assert_eq!(Score::median(&[]), None);
+        let scores = [Score::from(1), Score::from(2), Score::from(4)];
+        assert_eq!(Score::median(&scores), Some(Score::from(2)));
+        assert_eq!(Score::median(&scores[1..]), Some(Score::from(3)));
+    }
+
+    #[test]
+    fn display_pads_like_an_integer() {
+        assert_eq!(format!("{:>6}|", parse("7.5")), "   7.5|");
+        assert_eq!(format!("{:<6}|", Score::from(-12)), "-12   |");
+    }
+}
@@ -1,27 +1,26 @@
-use crate::Individual;
+use crate::alignment;
+use crate::conference;
 use crate::request;
 use crate::request::RequestFields;
-use crate::team::Team;
+use crate::results::ResultSet;
 use colored::Colorize;
 use rayon::prelude::*;
 use std::sync::{Arc, Mutex};
-use supports_color::Stream;
 
 pub fn scrape_subject(
     request_fields: RequestFields,
     mut conferences: Vec<u8>,
     mute: bool,
-) -> Option<(Vec<Individual>, Vec<Team>)> {
+) -> Option<ResultSet> {
     let district = request_fields.district;
     let region = request_fields.region;
     let state = request_fields.state;
     let subject = request_fields.subject;
     let year = request_fields.year;
 
-    let individual_results = Arc::new(Mutex::new(Vec::new()));
-    let team_results = Arc::new(Mutex::new(Vec::new()));
+    let results = Arc::new(Mutex::new(ResultSet::default()));
 
-    let mut fields = RequestFields {
+    let fields = RequestFields {
         district,
         region,
         state,
@@ -31,14 +30,21 @@ pub fn scrape_subject(
     };
 
     conferences.dedup();
+    conference::retain_existing(year, &mut conferences);
 
-    if district.is_some() && district.unwrap() == 0 {
+    if district == Some(0) {
         for conference in conferences.clone() {
-            fields.conference = conference;
-            let range = match region {
-                Some(0) => 1..=32,
-                Some(region) => (region * 8 - 7)..=(region * 8),
-                None => 1..=32,
+            let Some(range) = alignment::alignment(year, conference).districts(region) else {
+                eprintln!(
+                    "{}",
+                    format!(
+                        "{year} {} has no region {}",
+                        conference::label(year, conference),
+                        region.unwrap_or_default()
+                    )
+                    .red()
+                );
+                continue;
             };
             range.into_par_iter().for_each(|district| {
                 let fields = RequestFields {
@@ -50,10 +56,9 @@ pub fn scrape_subject(
                     year,
                 };
 
-                if let Some((mut individual, mut team)) = scrape(fields, mute) {
+                if let Some(mut scraped) = scrape(fields, mute) {
                     // Lock and modify safely
-                    individual_results.lock().unwrap().append(&mut individual);
-                    team_results.lock().unwrap().append(&mut team);
+                    results.lock().unwrap().append(&mut scraped);
                 }
             });
         }
@@ -61,14 +66,14 @@ pub fn scrape_subject(
         conferences.into_par_iter().for_each(|conference| {
             let mut fields = fields.clone();
             fields.conference = conference;
-            if district.is_some() || region.is_none() || region.is_some() && region.unwrap() != 0 {
-                if let Some((mut individual, mut team)) = scrape(fields, mute) {
+            if district.is_some() || region != Some(0) {
+                if let Some(mut scraped) = scrape(fields, mute) {
                     // Lock and modify safely
-                    individual_results.lock().unwrap().append(&mut individual);
-                    team_results.lock().unwrap().append(&mut team);
+                    results.lock().unwrap().append(&mut scraped);
                 }
-            } else if region.is_some() && region.unwrap() == 0 {
-                (1..=4).into_par_iter().for_each(|region| {
+            } else {
+                let range = alignment::alignment(year, conference).regions();
+                range.into_par_iter().for_each(|region| {
                     let fields = RequestFields {
                         subject: subject.clone(),
                         district: None,
@@ -78,68 +83,39 @@ pub fn scrape_subject(
                         year,
                     };
 
-                    if let Some((mut individual, mut team)) = scrape(fields, mute) {
+                    if let Some(mut scraped) = scrape(fields, mute) {
                         // Lock and modify safely
-                        individual_results.lock().unwrap().append(&mut individual);
-                        team_results.lock().unwrap().append(&mut team);
+                        results.lock().unwrap().append(&mut scraped);
                     }
                 });
             }
         });
     }
 
-    let individual_results: Vec<Individual> = individual_results.lock().ok()?.to_vec();
-    let team_results: Vec<Team> = team_results.lock().ok()?.to_vec();
+    let results: ResultSet = results.lock().ok()?.clone();
 
-    Some((individual_results, team_results))
+    Some(results)
 }
 
-pub fn scrape(fields: RequestFields, mute: bool) -> Option<(Vec<Individual>, Vec<Team>)> {
+pub fn scrape(fields: RequestFields, mute: bool) -> Option<ResultSet> {
     let conference = fields.conference;
-    let level;
+    let level = fields.level()?;
     let year = fields.year;
-    if fields.state {
-        level = String::from("States");
-    } else if fields.region.is_some() {
-        level = format!("Region {}", fields.region.unwrap());
-    } else if fields.district.is_some() {
-        level = format!("District {}", fields.district.unwrap());
-    } else {
-        return None;
-    }
     let subject = fields.subject.to_string();
-    let support = supports_color::on(Stream::Stdout);
-    let mut unavailable = format!("{year} {conference}A {subject} {level} unavailable").red();
-    let mut completed = format!("{year} {conference}A {subject} {level} completed").green();
-    match support {
-        Some(support) => {
-            if !support.has_basic {
-                unavailable.fgcolor = None;
-                unavailable.bgcolor = None;
-                completed.fgcolor = None;
-                completed.bgcolor = None;
-            }
-        }
-        _ => {
-            unavailable.fgcolor = None;
-            unavailable.bgcolor = None;
-            completed.fgcolor = None;
-            completed.bgcolor = None;
-        }
-    };
+    let conference = conference::label(year, conference);
+    let unavailable = format!("{year} {conference} {subject} {level} unavailable").red();
+    let completed = format!("{year} {conference} {subject} {level} completed").green();
 
-    let mut individual_results: Vec<Individual> = Vec::new();
-    let mut team_results: Vec<Team> = Vec::new();
+    let mut results = ResultSet::default();
 
-    if let Some((mut individual, mut team)) = request::perform_scrape(fields) {
-        individual_results.append(&mut individual);
-        team_results.append(&mut team);
+    if let Some(mut scraped) = request::perform_scrape(fields) {
+        results.append(&mut scraped);
         if !mute {
-            println!("{completed}");
+            eprintln!("{completed}");
         }
     } else if !mute {
-        println!("{unavailable}");
+        eprintln!("{unavailable}");
     }
 
-    Some((individual_results, team_results))
+    Some(results)
 }
@@ -0,0 +1,74 @@
+use std::cmp::Ordering;
+
+use clap::ValueEnum;
+
+use crate::{individual::Individual, team::Team};
+
+/// What result tables and exports are ordered by
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SortKey {
+    /// Highest score first
+    Score,
+    /// Most points first
+    Points,
+    /// Best place first (the same order as score, but ties stay together)
+    Place,
+    School,
+    /// Competitor name (school for team tables)
+    Name,
+    District,
+    Conference,
+}
+
+/// Reorders rows that already have their places. Rows that every key ties on keep
+/// their order, so the default (score, conference, school) breaks any remaining ties.
+pub fn sort_placed<P: Ord, T>(
+    rows: &mut [(P, T)],
+    keys: &[SortKey],
+    compare: impl Fn(&T, &T, SortKey) -> Ordering,
+) {
+    if keys.is_empty() {
+        return;
+    }
+    rows.sort_by(|(a_place, a), (b_place, b)| {
+        keys.iter().fold(Ordering::Equal, |ordering, key| {
+            ordering.then_with(|| match key {
+                SortKey::Place => a_place.cmp(b_place),
+                key => compare(a, b, *key),
+            })
+        })
+    });
+}
+
+/// Whether rows ordered by `keys` still run from the highest score down
+pub fn by_score(keys: &[SortKey]) -> bool {
+    keys.first()
+        .is_none_or(|key| matches!(key, SortKey::Score | SortKey::Place))
+}
+
+impl Individual {
+    pub fn compare(&self, other: &Self, key: SortKey) -> Ordering {
+        match key {
+            SortKey::Score => other.score.cmp(&self.score),
+            SortKey::Points => other.points.cmp(&self.points),
+            SortKey::Place => Ordering::Equal,
+            SortKey::School => self.school.cmp(&other.school),
+            SortKey::Name => self.name.cmp(&other.name),
+            SortKey::District => self.district.cmp(&other.district),
+            SortKey::Conference => self.conference.cmp(&other.conference),
+        }
+    }
+}
+
+impl Team {
+    pub fn compare(&self, other: &Self, key: SortKey) -> Ordering {
+        match key {
+            SortKey::Score => other.score.cmp(&self.score),
+            SortKey::Points => other.points.cmp(&self.points),
+            SortKey::Place => Ordering::Equal,
+            SortKey::School | SortKey::Name => self.school.cmp(&other.school),
+            SortKey::District => self.district.cmp(&other.district),
+            SortKey::Conference => self.conference.cmp(&other.conference),
+        }
+    }
+}
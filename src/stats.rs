@@ -0,0 +1,237 @@
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::{
+    cli::Cli,
+    output::{GroupBy, Report, emit_summary, print_aligned},
+    score::Score,
+};
+
+/// How many bars a histogram is split into
+const BINS: usize = 10;
+/// The widest a histogram bar is drawn
+const BAR_WIDTH: usize = 40;
+
+/// Descriptive statistics of one set of scores
+#[derive(Clone, Debug, Serialize)]
+pub struct Summary {
+    /// What the scores are from, e.g. "All" or "6A District 9"
+    pub group: String,
+    /// "individual" or "team"
+    pub kind: &'static str,
+    pub count: usize,
+    pub mean: f64,
+    pub median: Score,
+    /// Population standard deviation
+    pub stddev: f64,
+    pub lower_quartile: Score,
+    pub upper_quartile: Score,
+    pub min: Score,
+    pub max: Score,
+}
+
+impl Summary {
+    /// None when there are no scores to describe
+    fn new(group: &str, kind: &'static str, mut scores: Vec<Score>) -> Option<Self> {
+        scores.sort();
+        let count = scores.len();
+        let min = *scores.first()?;
+        let max = *scores.last()?;
+
+        let mean = scores.iter().map(|score| score.as_f64()).sum::<f64>() / count as f64;
+        let variance = scores
+            .iter()
+            .map(|score| (score.as_f64() - mean).powi(2))
+            .sum::<f64>()
+            / count as f64;
+
+        // Quartiles are the medians of the lower and upper halves, leaving out
+        // the middle score when there's an odd number
+        let half = count / 2;
+        let lower = &scores[..std::cmp::max(half, 1)];
+        let upper = &scores[std::cmp::min(count - half, count - 1)..];
+
+        Some(Self {
+            group: String::from(group),
+            kind,
+            count,
+            mean: round(mean),
+            median: Score::median(&scores)?,
+            stddev: round(variance.sqrt()),
+            lower_quartile: Score::median(lower)?,
+            upper_quartile: Score::median(upper)?,
+            min,
+            max,
+        })
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+fn scores(report: &Report) -> (Vec<Score>, Vec<Score>) {
+    (
+        report
+            .individuals
+            .iter()
+            .map(|individual| individual.score)
+            .collect(),
+        report.teams.iter().map(|team| team.score).collect(),
+    )
+}
+
+/// Statistics for the whole report, then broken down by conference and by district
+pub fn summaries(report: &Report) -> Vec<Summary> {
+    let (individuals, teams) = scores(report);
+    let mut groups = vec![(String::from("All"), individuals, teams)];
+    let mut breakdown = |group_by: GroupBy| {
+        for (group, report) in report.groups(group_by) {
+            let (individuals, teams) = scores(&report);
+            groups.push((group, individuals, teams));
+        }
+    };
+    if report.mixes_conferences() {
+        breakdown(GroupBy::Conference);
+    }
+    let has_districts = report
+        .individuals
+        .iter()
+        .any(|individual| individual.district.is_some())
+        || report.teams.iter().any(|team| team.district.is_some());
+    if has_districts {
+        breakdown(GroupBy::District);
+    }
+
+    let mut summaries = Vec::new();
+    for (group, individuals, teams) in groups {
+        summaries.extend(Summary::new(&group, "individual", individuals));
+        summaries.extend(Summary::new(&group, "team", teams));
+    }
+    summaries
+}
+
+/// Counts of scores in evenly sized ranges between the lowest and highest
+fn histogram(scores: &[Score]) -> Vec<(f64, f64, usize)> {
+    let Some(low) = scores.iter().min().map(|score| score.as_f64()) else {
+        return Vec::new();
+    };
+    let high = scores.iter().max().map_or(low, |score| score.as_f64());
+    let bins = if high > low { BINS } else { 1 };
+    let width = (high - low) / bins as f64;
+
+    let mut counts = vec![0; bins];
+    for score in scores {
+        let bin = if width > 0.0 {
+            ((score.as_f64() - low) / width) as usize
+        } else {
+            0
+        };
+        counts[std::cmp::min(bin, bins - 1)] += 1;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(bin, count)| {
+            (
+                low + width * bin as f64,
+                low + width * (bin + 1) as f64,
+                count,
+            )
+        })
+        .collect()
+}
+
+/// Prints the statistics as tables with histograms, or as JSON
+pub fn print(report: &Report, cli: &Cli) {
+    let summaries = summaries(report);
+
+    emit_summary("Statistics", &summaries, &summaries, cli, || {
+        print_tables(report, &summaries, cli)
+    });
+}
+
+fn print_tables(report: &Report, summaries: &[Summary], cli: &Cli) {
+    let (individuals, teams) = scores(report);
+    for (kind, title, scores) in [
+        ("individual", "Individual", individuals),
+        ("team", "Team", teams),
+    ] {
+        let rows: Vec<&Summary> = summaries
+            .iter()
+            .filter(|summary| summary.kind == kind)
+            .collect();
+        if rows.is_empty() {
+            continue;
+        }
+        println!("{} {title} Statistics:", report.subject.to_string());
+
+        if cli.plain {
+            for summary in rows {
+                println!(
+                    "{}; count: {}; mean: {}; median: {}; standard deviation: {}; lower quartile: {}; upper quartile: {}; min: {}; max: {}",
+                    summary.group,
+                    summary.count,
+                    summary.mean,
+                    summary.median,
+                    summary.stddev,
+                    summary.lower_quartile,
+                    summary.upper_quartile,
+                    summary.min,
+                    summary.max
+                );
+            }
+            for (low, high, count) in histogram(&scores) {
+                println!("histogram; {low:.0} to {high:.0}; count: {count}");
+            }
+            println!();
+            continue;
+        }
+
+        print_summaries(&rows);
+        println!();
+        print_histogram(&scores);
+        println!();
+    }
+}
+
+fn print_summaries(summaries: &[&Summary]) {
+    let headers = [
+        "Group", "Count", "Mean", "Median", "Std Dev", "Q1", "Q3", "Min", "Max",
+    ];
+    let rows: Vec<Vec<String>> = summaries
+        .iter()
+        .map(|summary| {
+            vec![
+                summary.group.clone(),
+                summary.count.to_string(),
+                format!("{:.2}", summary.mean),
+                summary.median.to_string(),
+                format!("{:.2}", summary.stddev),
+                summary.lower_quartile.to_string(),
+                summary.upper_quartile.to_string(),
+                summary.min.to_string(),
+                summary.max.to_string(),
+            ]
+        })
+        .collect();
+    print_aligned(&headers, &rows, 0);
+}
+
+fn print_histogram(scores: &[Score]) {
+    let bins = histogram(scores);
+    let most = bins.iter().map(|(_, _, count)| *count).max().unwrap_or(0);
+    let labels: Vec<String> = bins
+        .iter()
+        .map(|(low, high, _)| format!("{low:.0} to {high:.0}"))
+        .collect();
+    let label_length = labels.iter().map(String::len).max().unwrap_or(0);
+
+    for ((_, _, count), label) in bins.iter().zip(labels.iter()) {
+        let length = (count * BAR_WIDTH).div_ceil(std::cmp::max(most, 1));
+        println!(
+            "{label:>label_length$} │{} {count}",
+            "█".repeat(length).cyan()
+        );
+    }
+}
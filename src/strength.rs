@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::{
+    advance::AdvanceTypeIndividual,
+    cli::Cli,
+    competitor::CompetitorKey,
+    output::{GroupBy, Report, emit_summary, optional, print_aligned},
+    request::{Level, Subject},
+    score::Score,
+    scrape_meet,
+};
+
+/// How many of a meet's best individual scores are averaged
+const TOP_SCORES: usize = 5;
+/// Places at the next meet that count as doing well there
+const PODIUM: usize = 3;
+
+/// How competitive one district or region was, next to the others in its conference
+#[derive(Clone, Debug, Serialize)]
+pub struct Strength {
+    pub meet: String,
+    /// Place among the meets of the same conference, by `top_mean`. Ties share a rank.
+    pub rank: usize,
+    /// The mean of the best few individual scores
+    pub top_mean: f64,
+    /// The lowest individual score that advanced by placing
+    pub cutoff: Option<Score>,
+    pub best_team: Option<Score>,
+    /// How many individuals advanced, including as part of a team
+    pub advancers: usize,
+    /// How many advancers placed in the top three at the next meet
+    pub next_podiums: Option<usize>,
+    /// The advancers' average place at the next meet
+    pub next_mean_place: Option<f64>,
+}
+
+/// Ranks every district or region in the report within its conference. Advancers are
+/// looked up in `next`, the meet they went on to, when it could be scraped.
+pub fn strengths(
+    report: &Report,
+    group_by: GroupBy,
+    next: Option<&Report>,
+    cli: &Cli,
+) -> Vec<Strength> {
+    let matching = cli.matching();
+    let next_places: Option<HashMap<CompetitorKey, usize>> = next.map(|next| {
+        next.individuals
+            .iter()
+            .map(|individual| {
+                (
+                    CompetitorKey::individual(individual, matching),
+                    next.individual_place(individual),
+                )
+            })
+            .collect()
+    });
+
+    let mut strengths: Vec<(u8, Strength)> = Vec::new();
+    for (meet, group) in report.groups(group_by) {
+        let Some(conference) = group
+            .individuals
+            .iter()
+            .map(|individual| individual.conference)
+            .chain(group.teams.iter().map(|team| team.conference))
+            .next()
+        else {
+            continue;
+        };
+
+        let mut scores: Vec<Score> = group
+            .individuals
+            .iter()
+            .map(|individual| individual.score)
+            .collect();
+        scores.sort_by_key(|score| std::cmp::Reverse(*score));
+        scores.truncate(TOP_SCORES);
+        let top_mean = match scores.len() {
+            0 => 0.0,
+            count => scores.iter().map(|score| score.as_f64()).sum::<f64>() / count as f64,
+        };
+
+        let advancers: Vec<_> = group
+            .individuals
+            .iter()
+            .filter(|individual| individual.advance.is_some())
+            .collect();
+        let places: Option<Vec<usize>> = next_places.as_ref().map(|next_places| {
+            advancers
+                .iter()
+                .filter_map(|individual| {
+                    next_places
+                        .get(&CompetitorKey::individual(individual, matching))
+                        .copied()
+                })
+                .collect()
+        });
+
+        strengths.push((
+            conference,
+            Strength {
+                meet,
+                rank: 0,
+                top_mean: (top_mean * 100.0).round() / 100.0,
+                cutoff: group
+                    .individuals
+                    .iter()
+                    .filter(|individual| individual.advance == Some(AdvanceTypeIndividual::Indiv))
+                    .map(|individual| individual.score)
+                    .min(),
+                best_team: group.teams.iter().map(|team| team.score).max(),
+                advancers: advancers.len(),
+                next_podiums: places
+                    .as_ref()
+                    .map(|places| places.iter().filter(|place| **place <= PODIUM).count()),
+                next_mean_place: places.as_ref().and_then(|places| match places.len() {
+                    0 => None,
+                    count => {
+                        let mean = places.iter().sum::<usize>() as f64 / count as f64;
+                        Some((mean * 100.0).round() / 100.0)
+                    }
+                }),
+            },
+        ));
+    }
+
+    strengths.sort_by(|(a_conference, a), (b_conference, b)| {
+        b_conference
+            .cmp(a_conference)
+            .then(b.top_mean.total_cmp(&a.top_mean))
+    });
+    // Competition ranking within each conference, as with places: ties share a rank
+    // and the next rank skips past them
+    let mut seen = 0;
+    // conference, top mean, rank
+    let mut previous: Option<(u8, f64, usize)> = None;
+    for (conference, strength) in strengths.iter_mut() {
+        strength.rank = match previous {
+            Some((last_conference, last_mean, last_rank)) if last_conference == *conference => {
+                seen += 1;
+                if last_mean == strength.top_mean {
+                    last_rank
+                } else {
+                    seen
+                }
+            }
+            _ => {
+                seen = 1;
+                1
+            }
+        };
+        previous = Some((*conference, strength.top_mean, strength.rank));
+    }
+    strengths
+        .into_iter()
+        .map(|(_, strength)| strength)
+        .collect()
+}
+
+/// Scrapes the meet the report's advancers went on to, in every conference they came from
+fn next_meet(report: &Report, level: Level, cli: &Cli) -> Option<Report> {
+    let year = report
+        .individuals
+        .iter()
+        .map(|individual| individual.year)
+        .chain(report.teams.iter().map(|team| team.year))
+        .next()?;
+    let mut conferences: Vec<u8> = report
+        .individuals
+        .iter()
+        .map(|individual| individual.conference)
+        .chain(report.teams.iter().map(|team| team.conference))
+        .collect();
+    conferences.sort();
+    conferences.dedup();
+    scrape_meet(&report.subject, year, level, conferences, cli)
+}
+
+/// Ranks the districts and regions in district results, or the regions in region results
+pub fn print(report: &Report, cli: &Cli) {
+    if matches!(report.subject, Subject::Rankings | Subject::Sweepstakes) {
+        eprintln!("{}", "Strength only works for a single subject".red());
+        return;
+    }
+    let by_district = report
+        .individuals
+        .iter()
+        .any(|individual| individual.district.is_some())
+        || report.teams.iter().any(|team| team.district.is_some());
+    let by_region = report
+        .individuals
+        .iter()
+        .any(|individual| individual.region.is_some())
+        || report.teams.iter().any(|team| team.region.is_some());
+
+    // Each table's name, a note on what it measures, and its rows
+    let tables: Vec<(&str, Option<&str>, Vec<Strength>)> = if by_district {
+        let next = next_meet(report, Level::Region(0), cli);
+        vec![
+            (
+                "District",
+                None,
+                strengths(report, GroupBy::District, next.as_ref(), cli),
+            ),
+            (
+                "Region",
+                // Only the region meet is scraped, so state results don't count here
+                Some("from district results; the next meet is region, state isn't counted"),
+                strengths(report, GroupBy::Region, next.as_ref(), cli),
+            ),
+        ]
+    } else if by_region {
+        let next = next_meet(report, Level::State, cli);
+        vec![(
+            "Region",
+            None,
+            strengths(report, GroupBy::Region, next.as_ref(), cli),
+        )]
+    } else {
+        eprintln!(
+            "{}",
+            "Strength needs district or region results to compare".red()
+        );
+        return;
+    };
+
+    let mut document = serde_json::Map::new();
+    for (label, _, strengths) in tables.iter() {
+        document.insert(label.to_lowercase(), serde_json::json!(strengths));
+    }
+    let lines: Vec<&Strength> = tables
+        .iter()
+        .flat_map(|(_, _, strengths)| strengths)
+        .collect();
+    emit_summary("Strength rankings", &document, &lines, cli, || {
+        print_tables(report, &tables, cli)
+    });
+}
+
+fn print_tables(report: &Report, tables: &[(&str, Option<&str>, Vec<Strength>)], cli: &Cli) {
+    for (label, note, strengths) in tables {
+        match note {
+            Some(note) => println!("{} {label} Strength ({note}):", report.subject.to_string()),
+            None => println!("{} {label} Strength:", report.subject.to_string()),
+        }
+
+        if cli.plain {
+            for strength in strengths {
+                let mut fields = vec![
+                    strength.meet.clone(),
+                    format!("rank: {}", strength.rank),
+                    format!("top {TOP_SCORES} mean: {:.2}", strength.top_mean),
+                ];
+                if let Some(cutoff) = strength.cutoff {
+                    fields.push(format!("cutoff: {cutoff}"));
+                }
+                if let Some(best_team) = strength.best_team {
+                    fields.push(format!("best team: {best_team}"));
+                }
+                fields.push(format!("advancers: {}", strength.advancers));
+                if let Some(podiums) = strength.next_podiums {
+                    fields.push(format!("top {PODIUM} at next meet: {podiums}"));
+                }
+                if let Some(place) = strength.next_mean_place {
+                    fields.push(format!("average place at next meet: {place:.2}"));
+                }
+                println!("{}", fields.join("; "));
+            }
+            println!();
+            continue;
+        }
+
+        let top_header = format!("Top {TOP_SCORES} Mean");
+        let podium_header = format!("Next Top {PODIUM}");
+        let headers = [
+            "Rank",
+            "Meet",
+            top_header.as_str(),
+            "Cutoff",
+            "Best Team",
+            "Advancers",
+            podium_header.as_str(),
+            "Next Avg Place",
+        ];
+        let rows: Vec<Vec<String>> = strengths
+            .iter()
+            .map(|strength| {
+                vec![
+                    strength.rank.to_string(),
+                    strength.meet.clone(),
+                    format!("{:.2}", strength.top_mean),
+                    optional(strength.cutoff),
+                    optional(strength.best_team),
+                    strength.advancers.to_string(),
+                    optional(strength.next_podiums),
+                    optional(strength.next_mean_place.map(|place| format!("{place:.2}"))),
+                ]
+            })
+            .collect();
+        print_aligned(&headers, &rows, 1);
+        println!();
+    }
+}
@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{
+    competitor::{CompetitorKey, Matching},
+    individual::{Individual, IndividualMisc},
+    request::{Level, Subject},
+    results::{ResultSet, Source},
+    score::Score,
+    team::{Team, TeamMisc},
+};
+
+/// Individual points for 1st through 6th place
+const INDIVIDUAL_POINTS: [i64; 6] = [15, 12, 10, 8, 6, 4];
+/// Team points for 1st and 2nd place
+const TEAM_POINTS: [i64; 2] = [10, 5];
+/// Computer Science team points for 1st through 3rd place. The team score already
+/// includes the programming round, and programming places aren't scored on their own.
+const COMPUTER_SCIENCE_TEAM_POINTS: [i64; 3] = [20, 16, 12];
+
+/// The results of one event: every meet's individuals and teams for one subject
+pub struct Event {
+    pub subject: Subject,
+    pub individuals: Vec<Individual>,
+    pub teams: Vec<Team>,
+}
+
+/// A single meet, since points are only ever compared within one
+type Meet = (u8, Level);
+
+/// Points for each score (sorted best first) from a table of points by place.
+/// Tied competitors split the points of every place they share.
+fn award(scores: &[Score], table: &[i64]) -> Vec<Score> {
+    let mut points = Vec::with_capacity(scores.len());
+    let mut start = 0;
+    while start < scores.len() {
+        let end = scores[start..]
+            .iter()
+            .position(|score| *score != scores[start])
+            .map_or(scores.len(), |length| start + length);
+        let sum: i64 = table.iter().take(end).skip(start).sum();
+        let share = Score::from(sum).split(end - start);
+        points.extend(std::iter::repeat_n(share, end - start));
+        start = end;
+    }
+    points
+}
+
+impl Event {
+    /// Gives every individual and team their points, scoring each meet on its own
+    pub fn score(subject: Subject, individuals: Vec<Individual>, teams: Vec<Team>) -> Self {
+        let mut individual_meets: HashMap<Meet, Vec<Individual>> = HashMap::new();
+        for individual in individuals {
+            individual_meets
+                .entry((individual.conference, individual.level))
+                .or_default()
+                .push(individual);
+        }
+        let mut team_meets: HashMap<Meet, Vec<Team>> = HashMap::new();
+        for team in teams {
+            team_meets
+                .entry((team.conference, team.level))
+                .or_default()
+                .push(team);
+        }
+
+        let team_table: &[i64] = if subject == Subject::ComputerScience {
+            &COMPUTER_SCIENCE_TEAM_POINTS
+        } else {
+            &TEAM_POINTS
+        };
+
+        let mut scored_individuals = Vec::new();
+        for (_, mut meet) in individual_meets {
+            meet.sort_by_key(|competitor| std::cmp::Reverse(competitor.score));
+            let scores: Vec<Score> = meet.iter().map(|individual| individual.score).collect();
+            for (mut individual, points) in meet.into_iter().zip(award(&scores, &INDIVIDUAL_POINTS))
+            {
+                individual.points = points;
+                scored_individuals.push(individual);
+            }
+        }
+
+        let mut scored_teams = Vec::new();
+        for (_, mut meet) in team_meets {
+            meet.sort_by_key(|competitor| std::cmp::Reverse(competitor.score));
+            let scores: Vec<Score> = meet.iter().map(|team| team.score).collect();
+            for (mut team, points) in meet.into_iter().zip(award(&scores, team_table)) {
+                team.points = points;
+                scored_teams.push(team);
+            }
+        }
+
+        Self {
+            subject,
+            individuals: scored_individuals,
+            teams: scored_teams,
+        }
+    }
+}
+
+/// Totals every event's points for each individual and school, separately for each meet.
+/// Schools keep how many points each event earned them.
+pub fn totals(events: Vec<Event>, matching: Matching, sources: Vec<Arc<Source>>) -> ResultSet {
+    let breakdown: Vec<(Subject, ResultSet)> = events
+        .iter()
+        .map(|event| {
+            let results = ResultSet {
+                individuals: event.individuals.clone(),
+                teams: event.teams.clone(),
+                sources: sources
+                    .iter()
+                    .filter(|source| source.subject == event.subject)
+                    .cloned()
+                    .collect(),
+                events: Vec::new(),
+            };
+            (event.subject.clone(), results)
+        })
+        .collect();
+
+    let mut individuals: Vec<Individual> = Vec::new();
+    let mut individual_index: HashMap<(CompetitorKey, Level), usize> = HashMap::new();
+    let mut schools: Vec<Team> = Vec::new();
+    let mut school_index: HashMap<(CompetitorKey, Level), usize> = HashMap::new();
+
+    let mut add_school = |template: Team, key: CompetitorKey, subject: &Subject, points: Score| {
+        let index = *school_index
+            .entry((key, template.level))
+            .or_insert_with(|| {
+                schools.push(Team {
+                    score: Score::default(),
+                    points: Score::default(),
+                    advance: None,
+                    misc: TeamMisc::Sweepstakes { events: Vec::new() },
+                    ..template
+                });
+                schools.len() - 1
+            });
+        let school = &mut schools[index];
+        school.points += points;
+        school.score = school.points;
+        if let TeamMisc::Sweepstakes { events } = &mut school.misc {
+            match events.iter_mut().find(|(event, _)| event == subject) {
+                Some((_, total)) => *total += points,
+                None => events.push((subject.clone(), points)),
+            }
+        }
+    };
+
+    for event in events {
+        for individual in event.individuals {
+            if individual.points == Score::default() {
+                continue;
+            }
+            let school = Team {
+                school: individual.school.clone(),
+                conference: individual.conference,
+                district: individual.district,
+                region: individual.region,
+                year: individual.year,
+                level: individual.level,
+                score: Score::default(),
+                points: Score::default(),
+                advance: None,
+                misc: TeamMisc::Normal,
+                source: individual.source.clone(),
+            };
+            add_school(
+                school,
+                CompetitorKey::team_of(&individual, matching),
+                &event.subject,
+                individual.points,
+            );
+
+            let key = (
+                CompetitorKey::individual(&individual, matching),
+                individual.level,
+            );
+            match individual_index.get(&key) {
+                Some(index) => individuals[*index].points += individual.points,
+                None => {
+                    individual_index.insert(key, individuals.len());
+                    individuals.push(Individual {
+                        advance: None,
+                        misc: IndividualMisc::Normal,
+                        ..individual
+                    });
+                }
+            }
+        }
+
+        for team in event.teams {
+            if team.points == Score::default() {
+                continue;
+            }
+            let key = CompetitorKey::team(&team, matching);
+            let points = team.points;
+            add_school(team, key, &event.subject, points);
+        }
+    }
+
+    for individual in individuals.iter_mut() {
+        individual.score = individual.points;
+    }
+
+    ResultSet {
+        individuals,
+        teams: schools,
+        sources,
+        events: breakdown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scores(scores: &[i64]) -> Vec<Score> {
+        scores.iter().map(|score| Score::from(*score)).collect()
+    }
+
+    #[test]
+    fn awards_places_in_order() {
+        let points = award(&scores(&[90, 80, 70, 60, 50, 40, 30]), &INDIVIDUAL_POINTS);
+        assert_eq!(points, scores(&[15, 12, 10, 8, 6, 4, 0]));
+    }
+
+    #[test]
+    fn tie_across_the_last_place_shares_its_points() {
+        let points = award(&scores(&[90, 80, 70, 60, 50, 40, 40]), &INDIVIDUAL_POINTS);
+        assert_eq!(points[5], Score::from(2));
+        assert_eq!(points[6], Score::from(2));
+    }
+
+    #[test]
+    fn three_way_tie_for_first_splits_three_places() {
+        let points = award(&scores(&[90, 90, 90, 80]), &INDIVIDUAL_POINTS);
+        let share = Score::from(37).split(3);
+        assert_eq!(points, vec![share, share, share, Score::from(8)]);
+        assert_eq!(share.to_string(), "12.33");
+    }
+}
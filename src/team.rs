@@ -1,73 +1,68 @@
-use colored::{Color, ColoredString, Colorize};
+use colored::{ColoredString, Colorize};
 use scraper::{ElementRef, Selector};
-use std::{cmp, collections::HashMap};
-use supports_color::Stream;
+use std::{collections::HashMap, sync::Arc};
 
 use crate::{
     advance::AdvanceTypeTeam,
-    request::{RequestFields, Subject, district_as_region},
+    output::{conference_places, percentile},
+    request::{Level, RequestFields, Subject, district_as_region},
+    results::Source,
+    school,
+    score::Score,
+    sort::{SortKey, by_score, sort_placed},
+    theme,
 };
 
 #[derive(Clone, PartialEq, PartialOrd, Debug)]
 pub struct Team {
     pub school: String,
-    pub score: i16,
+    pub score: Score,
     pub conference: u8,
     pub district: Option<u8>,
     pub region: Option<u8>,
-    pub points: f32,
+    pub year: u16,
+    pub level: Level,
+    pub points: Score,
     pub advance: Option<AdvanceTypeTeam>,
     pub misc: TeamMisc,
+    /// The page this row was scraped from
+    pub source: Option<Arc<Source>>,
 }
 
 #[derive(Clone, Eq, PartialEq, PartialOrd, Debug)]
 pub enum TeamMisc {
     Normal,
 
-    ComputerScience { prog: Option<i16> },
-}
+    ComputerScience {
+        prog: Option<Score>,
+    },
 
-impl Default for Team {
-    fn default() -> Self {
-        Self {
-            school: String::new(),
-            district: None,
-            region: None,
-            conference: 0,
-            score: 0,
-            points: 0.0,
-            advance: None,
-            misc: TeamMisc::Normal,
-        }
-    }
+    /// A school's sweepstakes total, with the points each event earned it
+    Sweepstakes {
+        events: Vec<(Subject, Score)>,
+    },
 }
 
 impl Team {
-    pub const fn get_prog(&self) -> Option<i16> {
+    pub const fn get_prog(&self) -> Option<Score> {
         match self.misc {
-            TeamMisc::Normal => None,
             TeamMisc::ComputerScience { prog } => prog,
+            _ => None,
         }
     }
 
-    pub fn get_ties(sorted: Vec<Self>) -> Vec<Vec<Self>> {
-        let mut groups: Vec<Vec<Self>> = Vec::new();
-        let mut current_group: Vec<Self> = Vec::new();
-
-        for (i, team) in sorted.iter().enumerate() {
-            if i == 0 || team.score == sorted[i - 1].score {
-                current_group.push(team.clone());
-            } else {
-                groups.push(current_group);
-                current_group = vec![team.clone()];
-            }
-        }
-
-        if !current_group.is_empty() {
-            groups.push(current_group);
+    /// The points each event earned a school in sweepstakes, like "Accounting 25; Spelling 10"
+    pub fn events_summary(&self) -> Option<String> {
+        match &self.misc {
+            TeamMisc::Sweepstakes { events } => Some(
+                events
+                    .iter()
+                    .map(|(subject, points)| format!("{} {points}", subject.to_string()))
+                    .collect::<Vec<_>>()
+                    .join("; "),
+            ),
+            _ => None,
         }
-
-        groups
     }
 
     pub fn parse_table(table: ElementRef, fields: &RequestFields) -> Option<Vec<Self>> {
@@ -77,6 +72,8 @@ impl Team {
         let cell_selector = Selector::parse("td").ok()?;
         let span_selector = Selector::parse("span").unwrap();
 
+        let level = fields.level()?;
+
         let mut place_index = 0;
         let mut points_index = 0;
         let mut advance_index = 0;
@@ -124,13 +121,11 @@ impl Team {
             if fields.year > 2022 {
                 let _ = school.split_off(school.find(&span_text).unwrap());
             } else {
+                // Legacy postings add the city after the school
                 let _ = school.split_off(school.find(", ").unwrap_or(school.len()));
-                if school.split_off(school.find("H S").unwrap_or(school.len())) == "H S" {
-                    school += "HS";
-                }
             }
 
-            let school = school.trim().to_string();
+            let school = school::normalize(school.trim());
 
             let district = fields.district;
             let region = fields.region;
@@ -140,11 +135,10 @@ impl Team {
                 }
                 _ => &cells[score_index],
             }
-            .trim()
-            .parse::<f32>()
-            .unwrap_or(0.0) as i16;
+            .trim();
+            let score = Score::parse(score).unwrap_or_default();
 
-            let points = cells[points_index].parse::<f32>().unwrap_or(0.0);
+            let points = Score::parse(&cells[points_index]).unwrap_or_default();
 
             let advance_str = if advance_index != 0 {
                 &cells[advance_index]
@@ -159,9 +153,7 @@ impl Team {
 
             let misc = match fields.clone().subject {
                 Subject::ComputerScience => TeamMisc::ComputerScience {
-                    prog: cells[if fields.year > 2022 { 2 } else { 0 }]
-                        .parse::<i16>()
-                        .ok(),
+                    prog: Score::parse(&cells[if fields.year > 2022 { 2 } else { 0 }]),
                 },
                 _ => TeamMisc::Normal {},
             };
@@ -172,9 +164,12 @@ impl Team {
                 conference: fields.clone().conference,
                 district,
                 region,
+                year: fields.year,
+                level,
                 points,
                 advance,
                 misc,
+                source: None,
             };
 
             results.push(team);
@@ -182,14 +177,8 @@ impl Team {
         Some(results)
     }
 
-    pub fn display_results(
-        mut results: Vec<Self>,
-        subject: Subject,
-        positions: usize,
-        find: &Option<String>,
-    ) {
-        let support = supports_color::on(Stream::Stdout);
-
+    /// Sorts results by score and drops duplicates
+    pub fn sort_results(results: &mut Vec<Self>) {
         results.sort_by(|a, b| {
             let a_score = a.score;
             let b_score = b.score;
@@ -197,6 +186,36 @@ impl Team {
         });
 
         results.dedup();
+    }
+
+    /// The place of each sorted result, starting at 1, with tied scores sharing a place
+    pub fn places(sorted: &[Self]) -> Vec<usize> {
+        let mut places = Vec::new();
+        for (index, team) in sorted.iter().enumerate() {
+            if index > 0 && team.score == sorted[index - 1].score {
+                places.push(places[index - 1]);
+            } else {
+                places.push(index + 1);
+            }
+        }
+        places
+    }
+
+    pub fn matches(&self, find: &str) -> bool {
+        self.school.contains(find)
+    }
+
+    pub fn display_results(
+        mut results: Vec<Self>,
+        subject: Subject,
+        positions: usize,
+        find: &Option<String>,
+        sort: &[SortKey],
+        cutoff: bool,
+    ) {
+        let theme = theme::theme();
+
+        Self::sort_results(&mut results);
 
         let mut longest_team_name = 0;
         for team in results.iter() {
@@ -214,168 +233,121 @@ impl Team {
             longest_team_name = team.school.len();
         }
 
-        let place_length = if find.is_none() {
-            results.len().checked_ilog10().unwrap_or(0) as usize + 1
+        // Results from different conferences also get their place within their own conference
+        let mixed = results
+            .iter()
+            .any(|team| Some(team.conference) != results.first().map(|first| first.conference));
+        let count = results.len();
+        let places = Self::places(&results);
+        let conference_places = conference_places(&results, |team| (team.conference, team.score));
+        let mut placed: Vec<((usize, usize), Self)> = places
+            .into_iter()
+            .zip(conference_places)
+            .zip(results)
+            .collect();
+        sort_placed(&mut placed, sort, Self::compare);
+
+        let place_length = placed
+            .iter()
+            .filter(|(_, team)| find.as_ref().is_none_or(|find| team.matches(find)))
+            .map(|((place, _), _)| place.checked_ilog10().unwrap_or(0) as usize + 1)
+            .max()
+            .unwrap_or(1);
+        let conference_place_length = placed
+            .iter()
+            .map(|((_, place), _)| place.checked_ilog10().unwrap_or(0) as usize + 1)
+            .max()
+            .unwrap_or(1);
+
+        let score_length = placed
+            .iter()
+            .map(|(_, team)| team.score.to_string().len())
+            .max()
+            .unwrap_or(1);
+
+        // The line under the last advancing row, when each meet is shown on its own.
+        // It's found before rows are left out, and only means anything in score order.
+        let last_advancing = if cutoff && by_score(sort) {
+            placed.iter().rposition(|(_, team)| team.advance.is_some())
         } else {
-            let mut previous_score = results.first().unwrap().score;
-            let mut previous_place = 0;
-            let mut longest_place = 0;
-            for (place, team) in results.iter().enumerate() {
-                let school = team.school.clone();
-                let score = team.score;
-
-                let place = if score == previous_score {
-                    previous_place
-                } else {
-                    place
-                };
-
-                if score != previous_score {
-                    previous_score = score;
-                }
-                previous_place = place;
-
-                if school.contains(&find.clone().unwrap()) {
-                    longest_place = place;
-                }
-            }
-            longest_place.checked_ilog10().unwrap_or(0) as usize + 1
+            None
         };
+        let cutoff_line = "-".repeat(place_length + longest_team_name + score_length + 5);
+        let mut shown_above = false;
+        let mut cut = false;
 
-        let first = results.first().unwrap();
-        let score_length = first.score.checked_ilog10().unwrap_or(0) as usize + 1;
-        let mut previous_score = first.score;
-        let mut previous_place = 0;
-
-        for (place, team) in results.iter().enumerate() {
+        for (index, ((place, conference_place), team)) in placed.iter().enumerate() {
+            // Places start at 1, but the rest of the layout counts from 0
+            let place = place - 1;
             let school = team.school.clone();
             let score = team.score;
 
-            let place = if score == previous_score {
-                previous_place
-            } else {
-                place
-            };
-
-            if score != previous_score {
-                previous_score = score;
+            if positions != 0 && find.is_none() && place >= positions {
+                continue;
             }
-            previous_place = place;
 
-            if positions != 0 && find.is_none() && place >= cmp::min(results.len(), positions) {
-                break;
+            if let Some(find_name) = find.clone()
+                && !school.contains(&find_name)
+            {
+                continue;
             }
 
-            if let Some(find_name) = find.clone() {
-                if !school.contains(&find_name) {
-                    continue;
+            if let Some(last_advancing) = last_advancing {
+                if index > last_advancing && shown_above && !cut {
+                    println!("{cutoff_line}");
+                    cut = true;
                 }
+                shown_above |= index <= last_advancing;
             }
 
-            let mut base: ColoredString = format!(
-                "{:place_length$} {:longest_team_name$} => {:>score_length$}",
+            let mut base = theme.medal(
+                format!(
+                    "{:place_length$} {:longest_team_name$} => {:>score_length$}",
+                    place + 1,
+                    school,
+                    score
+                ),
                 place + 1,
-                school,
-                score
-            )
-            .into();
-            match place + 1 {
-                1 => {
-                    base.fgcolor = Some(Color::Black);
-                    base.bgcolor = Some(Color::Yellow);
-                }
-                2 => {
-                    base.fgcolor = Some(Color::Black);
-                    base.bgcolor = Some(Color::BrightWhite);
-                }
-                3 => {
-                    base.fgcolor = Some(Color::Black);
-                    base.bgcolor = Some(Color::BrightRed);
-                }
-
-                _ => base.fgcolor = None,
-            };
+            );
+            if mixed {
+                base.input = format!("{} {:>5.1}%", base.input, percentile(place + 1, count));
+            }
 
+            let prog_length = std::cmp::max(
+                placed
+                    .iter()
+                    .filter_map(|(_, team)| team.get_prog())
+                    .map(|prog| prog.to_string().len())
+                    .max()
+                    .unwrap_or(0),
+                "N/A".len(),
+            );
             if let Some(prog) = team.get_prog() {
-                let prog_length = std::cmp::max(
-                    first.get_prog().unwrap_or(0).checked_ilog10().unwrap_or(0) as usize + 1,
-                    "N/A".len(),
-                );
                 base.input = format!("{} (prog {:>prog_length$})", base.input, prog);
             } else if matches!(subject, Subject::ComputerScience) {
-                let prog_length = std::cmp::max(
-                    first.get_prog().unwrap_or(1).checked_ilog10().unwrap_or(0) as usize + 1,
-                    "N/A".len(),
-                );
                 base.input = format!("{} (prog {:prog_length$})", base.input, "N/A");
             }
             let conference = team.conference;
 
-            let mut conference_str: ColoredString = match conference {
-                1 => "1A".white(),
-                2 => "2A".yellow(),
-                3 => "3A".bright_blue(),
-                4 => "4A".green(),
-                5 => "5A".red(),
-                6 => "6A".magenta(),
-                _ => "".into(),
-            };
-
-            let advance = team.advance.clone();
-
-            let mut advance_status = "".green();
-            if advance.is_some() {
-                let advance = advance.unwrap();
-                if advance == AdvanceTypeTeam::Advance {
-                    advance_status = "(Advanced)".green();
-                } else {
-                    advance_status = "(Wildcard)".truecolor(0xFF, 0xA5, 0x00);
-                }
+            let mut conference_str = theme.conference(team.year, conference);
+            if mixed {
+                conference_str.input = format!(
+                    "{} #{conference_place:<conference_place_length$}",
+                    conference_str.input
+                );
             }
-            match support {
-                Some(support) => {
-                    if !support.has_basic {
-                        base.fgcolor = None;
-                        base.bgcolor = None;
-                        conference_str.fgcolor = None;
-                        conference_str.bgcolor = None;
-                        advance_status.fgcolor = None;
-                        advance_status.bgcolor = None;
-                    }
-                }
-                _ => {
-                    base.fgcolor = None;
-                    base.bgcolor = None;
-                    conference_str.fgcolor = None;
-                    conference_str.bgcolor = None;
-                    advance_status.fgcolor = None;
-                    advance_status.bgcolor = None;
+
+            let advance_status: ColoredString = match &team.advance {
+                Some(advance) => {
+                    let (label, color) = theme.team_badge(advance);
+                    format!("({label})").color(color)
                 }
+                None => "".into(),
             };
-
             if let Some(district) = team.district {
-                let region = district_as_region(Some(district)).unwrap_or(0);
-
-                let mut region_str: ColoredString = match region {
-                    1 => "Region 1".red(),
-                    2 => "Region 2".yellow(),
-                    3 => "Region 3".green(),
-                    4 => "Region 4".blue(),
-                    _ => "".into(),
-                };
-
-                match support {
-                    Some(support) => {
-                        if !support.has_basic {
-                            region_str.fgcolor = None;
-                            region_str.bgcolor = None;
-                        }
-                    }
-                    _ => {
-                        region_str.fgcolor = None;
-                        region_str.bgcolor = None;
-                    }
-                }
+                let region = district_as_region(Some(district), team.year, conference).unwrap_or(0);
+
+                let region_str = theme.region(format!("Region {region}"), region);
 
                 println!(
                     "{base} {conference_str} - District {district:<2} {region_str} {advance_status}"
@@ -385,6 +357,13 @@ impl Team {
             } else {
                 println!("{base} {conference_str}");
             }
+
+            if let Some(events) = team.events_summary() {
+                println!("{:indent$}{events}", "", indent = place_length + 1);
+            }
+        }
+        if shown_above && !cut {
+            println!("{cutoff_line}");
         }
     }
 
@@ -397,51 +376,50 @@ impl Team {
 
         results.dedup();
 
+        // u16: year
+        // Level: the meet
         // u8: district or region
         // u8: conference
-        // i16: score
-        let mut winning_teams: HashMap<(u8, u8), Self> = HashMap::new();
+        // Self: the winning team
+        let mut winning_teams: HashMap<(u16, Level, u8, u8), Self> = HashMap::new();
         for team in results.iter() {
             let location = team.district.unwrap_or(team.region.unwrap_or(0));
             winning_teams
-                .entry((location, team.conference))
+                .entry((team.year, team.level, location, team.conference))
                 .or_insert(team.clone());
         }
 
+        // u16: year
+        // Level: the meet
         // u8: region or district
         // u8: conference
-        // i16: score
-        let mut wildcarding_teams: HashMap<(u8, u8), Self> = HashMap::new();
+        // Self: the best team that didn't win
+        let mut wildcarding_teams: HashMap<(u16, Level, u8, u8), Self> = HashMap::new();
 
         for team in results.iter() {
-            if team.district.is_some() {
-                let location = team.district.unwrap();
+            if let Some(location) = team.district {
                 if winning_teams
-                    .get(&(location, team.conference))
+                    .get(&(team.year, team.level, location, team.conference))
                     .unwrap()
                     .score
                     > team.score
                 {
-                    let region_value = district_as_region(Some(location)).unwrap();
-                    let result =
-                        wildcarding_teams.insert((region_value, team.conference), team.clone());
-                    if let Some(old_value) = result {
-                        wildcarding_teams.insert((region_value, team.conference), old_value);
-                    }
+                    let region_value =
+                        district_as_region(Some(location), team.year, team.conference).unwrap_or(0);
+                    wildcarding_teams
+                        .entry((team.year, team.level, region_value, team.conference))
+                        .or_insert(team.clone());
                 }
-            } else if team.region.is_some() {
-                let location = team.region.unwrap();
-                if winning_teams
-                    .get(&(location, team.conference))
+            } else if let Some(location) = team.region
+                && winning_teams
+                    .get(&(team.year, team.level, location, team.conference))
                     .unwrap()
                     .score
                     > team.score
-                {
-                    let result = wildcarding_teams.insert((1, team.conference), team.clone());
-                    if let Some(old_value) = result {
-                        wildcarding_teams.insert((1, team.conference), old_value);
-                    }
-                }
+            {
+                wildcarding_teams
+                    .entry((team.year, team.level, 1, team.conference))
+                    .or_insert(team.clone());
             }
         }
 
@@ -0,0 +1,239 @@
+use std::{collections::HashMap, fs, str::FromStr, sync::OnceLock};
+
+use clap::ValueEnum;
+use colored::{Color, ColoredString, Colorize};
+use supports_color::Stream;
+
+use crate::{
+    advance::{AdvanceTypeIndividual, AdvanceTypeTeam},
+    conference,
+    output::Medal,
+};
+
+const BUILT_IN_THEME: &str = include_str!("../data/theme.txt");
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Colors when the terminal supports them
+    #[default]
+    Auto,
+    /// Colors even when piping, e.g. into `less -R`
+    Always,
+    /// No colors at all, e.g. for CI logs
+    Never,
+}
+
+/// Every color and advancement label that results are shown with
+#[derive(Default, Debug)]
+pub struct Theme {
+    conferences: HashMap<u8, Color>,
+    regions: HashMap<u8, Color>,
+    medals: HashMap<String, Color>,
+    /// Status -> (label, color)
+    advance: HashMap<String, (String, Color)>,
+}
+
+impl Theme {
+    pub fn built_in() -> Self {
+        let mut theme = Self::default();
+        theme
+            .add(BUILT_IN_THEME)
+            .expect("Built-in theme is malformed");
+        theme
+    }
+
+    /// Adds the entries from a theme file, with later entries taking priority
+    pub fn add(&mut self, text: &str) -> Result<(), String> {
+        let mut section = "";
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                section = match &line[1..line.len() - 1] {
+                    "conferences" => "conferences",
+                    "regions" => "regions",
+                    "medals" => "medals",
+                    "advance" => "advance",
+                    other => return Err(format!("line {}: unknown section [{other}]", number + 1)),
+                };
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(format!("line {}: expected `<name> = <value>`", number + 1));
+            };
+            let (key, value) = (key.trim().to_lowercase(), value.trim());
+            let error = |message: &str| format!("line {}: {message}", number + 1);
+
+            match section {
+                "conferences" | "regions" => {
+                    let number = key
+                        .trim_end_matches('a')
+                        .parse::<u8>()
+                        .map_err(|_| error(&format!("expected a number, found `{key}`")))?;
+                    let color =
+                        parse_color(value).ok_or(error(&format!("unknown color `{value}`")))?;
+                    if section == "conferences" {
+                        self.conferences.insert(number, color);
+                    } else {
+                        self.regions.insert(number, color);
+                    }
+                }
+                "medals" => {
+                    let color =
+                        parse_color(value).ok_or(error(&format!("unknown color `{value}`")))?;
+                    self.medals.insert(key, color);
+                }
+                "advance" => {
+                    let Some((label, color)) = value.rsplit_once(',') else {
+                        return Err(error("expected `<status> = <label>, <color>`"));
+                    };
+                    let color = color.trim();
+                    let color =
+                        parse_color(color).ok_or(error(&format!("unknown color `{color}`")))?;
+                    self.advance.insert(key, (label.trim().to_string(), color));
+                }
+                _ => return Err(error("entry outside of a section")),
+            }
+        }
+        Ok(())
+    }
+
+    pub fn conference_color(&self, code: u8) -> Option<Color> {
+        self.conferences.get(&code).copied()
+    }
+
+    /// A conference's label in its color, empty when the conference isn't known (0)
+    pub fn conference(&self, year: u16, code: u8) -> ColoredString {
+        if code == 0 {
+            return "".into();
+        }
+        paint(conference::label(year, code), self.conference_color(code))
+    }
+
+    /// A region's label, like "R2" or "Region 2", in the region's color
+    pub fn region(&self, label: String, region: u8) -> ColoredString {
+        if region == 0 {
+            return "".into();
+        }
+        paint(label, self.regions.get(&region).copied())
+    }
+
+    pub fn medal_color(&self, medal: Medal) -> Color {
+        self.medals
+            .get(medal.name())
+            .copied()
+            .unwrap_or(Color::BrightWhite)
+    }
+
+    /// Highlights a row if its place gets a medal
+    pub fn medal(&self, text: String, place: usize) -> ColoredString {
+        match Medal::from_place(place) {
+            Some(medal) => text
+                .color(self.medals.get("text").copied().unwrap_or(Color::Black))
+                .on_color(self.medal_color(medal)),
+            None => text.into(),
+        }
+    }
+
+    fn advance(&self, status: &str) -> (String, Color) {
+        self.advance
+            .get(status)
+            .cloned()
+            .unwrap_or((status.to_string(), Color::White))
+    }
+
+    /// The label and color shown for an advancing individual
+    pub fn individual_badge(&self, advance: &AdvanceTypeIndividual) -> (String, Color) {
+        self.advance(match advance {
+            AdvanceTypeIndividual::Indiv => "individual",
+            AdvanceTypeIndividual::Team => "team",
+            AdvanceTypeIndividual::Wild => "wildcard",
+        })
+    }
+
+    /// The label and color shown for an advancing team
+    pub fn team_badge(&self, advance: &AdvanceTypeTeam) -> (String, Color) {
+        self.advance(match advance {
+            AdvanceTypeTeam::Advance => "advanced",
+            AdvanceTypeTeam::Alternate => "alternate",
+        })
+    }
+}
+
+fn paint(text: String, color: Option<Color>) -> ColoredString {
+    match color {
+        Some(color) => text.color(color),
+        None => text.into(),
+    }
+}
+
+/// Parses a color name like "bright blue" or a hex code like "#ffa500"
+pub fn parse_color(string: &str) -> Option<Color> {
+    if let Some(hex) = string.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+        let channel = |index: usize| u8::from_str_radix(hex.get(index..index + 2)?, 16).ok();
+        return Some(Color::TrueColor {
+            r: channel(0)?,
+            g: channel(2)?,
+            b: channel(4)?,
+        });
+    }
+    Color::from_str(string).ok()
+}
+
+/// The red, green and blue a color is drawn with outside the terminal, in HTML and
+/// spreadsheets
+pub const fn rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::Black => (0x00, 0x00, 0x00),
+        Color::Red => (0xcd, 0x31, 0x31),
+        Color::Green => (0x23, 0xa5, 0x59),
+        Color::Yellow => (0xe5, 0xc1, 0x00),
+        Color::Blue => (0x24, 0x72, 0xc8),
+        Color::Magenta => (0xbc, 0x3f, 0xbc),
+        Color::Cyan => (0x11, 0xa8, 0xcd),
+        Color::White => (0xe5, 0xe5, 0xe5),
+        Color::BrightBlack => (0x66, 0x66, 0x66),
+        Color::BrightRed => (0xf1, 0x4c, 0x4c),
+        Color::BrightGreen => (0x23, 0xd1, 0x8b),
+        Color::BrightYellow => (0xf5, 0xf5, 0x43),
+        Color::BrightBlue => (0x3b, 0x8e, 0xea),
+        Color::BrightMagenta => (0xd6, 0x70, 0xd6),
+        Color::BrightCyan => (0x29, 0xb8, 0xdb),
+        Color::BrightWhite => (0xf5, 0xf5, 0xf5),
+        Color::TrueColor { r, g, b } => (r, g, b),
+    }
+}
+
+/// Loads the built-in theme plus the one in `path`, if given, and decides whether
+/// anything gets colored at all
+pub fn load(path: Option<&str>, choice: ColorChoice) -> Result<(), String> {
+    let enabled = match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            supports_color::on(Stream::Stdout).is_some_and(|support| support.has_basic)
+        }
+    };
+    colored::control::set_override(enabled);
+
+    let mut theme = Theme::built_in();
+    if let Some(path) = path {
+        let text = fs::read_to_string(path).map_err(|err| format!("{path}: {err}"))?;
+        theme.add(&text).map_err(|err| format!("{path}: {err}"))?;
+    }
+    THEME
+        .set(theme)
+        .map_err(|_| String::from("the theme was already loaded"))
+}
+
+pub fn theme() -> &'static Theme {
+    THEME.get_or_init(Theme::built_in)
+}
@@ -0,0 +1,247 @@
+use std::path::PathBuf;
+
+use chrono::Datelike;
+use colored::Colorize;
+use serde::Serialize;
+
+use crate::{
+    cli::Cli,
+    competitor::normalize_school,
+    finish_results,
+    output::{Format, emit_summary, file_stem, optional, print_aligned},
+    overall,
+    request::{RequestFields, Subject},
+    score::Score,
+};
+
+/// Bars for sparklines, lowest first
+const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One season's numbers for a school, or for everyone at the level
+#[derive(Clone, Debug, Serialize)]
+pub struct Point {
+    pub year: u16,
+    /// The school's team score, or the best team score when following a subject
+    pub team_score: Option<Score>,
+    pub best_individual: Option<Score>,
+    /// Who scored the best individual score
+    pub best_name: Option<String>,
+    /// The median individual score
+    pub median: Option<Score>,
+    /// How many individuals advanced
+    pub advancers: usize,
+}
+
+/// A line of bars scaled between the lowest and highest value, with gaps for missing years
+pub fn sparkline(values: &[Option<f64>]) -> String {
+    let present = values.iter().flatten();
+    let low = present.clone().copied().fold(f64::INFINITY, f64::min);
+    let high = present.copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|value| match value {
+            Some(_) if high <= low => BARS[BARS.len() / 2],
+            Some(value) => {
+                let step = ((value - low) / (high - low) * (BARS.len() - 1) as f64).round();
+                BARS[step as usize]
+            }
+            None => ' ',
+        })
+        .collect()
+}
+
+/// Scrapes the same level for every year in the range and sums up each one,
+/// for one school or for the whole subject
+pub fn run(
+    subject: Subject,
+    school: Option<&str>,
+    years: Option<String>,
+    fields: RequestFields,
+    conferences: Vec<u8>,
+    cli: &Cli,
+) {
+    if matches!(subject, Subject::Rankings | Subject::Sweepstakes) {
+        eprintln!("{}", "Trends only work for a single subject".red());
+        return;
+    }
+
+    let current: u16 = chrono::Utc::now().year().try_into().unwrap_or(2004);
+    let years = match years {
+        Some(years) => match RequestFields::parse_years(&years) {
+            Some(years) => years,
+            None => {
+                eprintln!("{}", format!("Unknown years {years}").red());
+                return;
+            }
+        },
+        None => current - 3..=current,
+    };
+    // Seasons before a test changed scales can't be compared
+    let supported = overall::years(&subject, fields.region.is_some());
+    let range = std::cmp::max(*years.start(), *supported.start())
+        ..=std::cmp::min(*years.end(), *supported.end());
+
+    let wanted = school.map(normalize_school);
+    let is_ours = |school: &str| {
+        wanted
+            .as_ref()
+            .is_none_or(|wanted| normalize_school(school) == *wanted)
+    };
+
+    let mut points: Vec<Point> = Vec::new();
+    overall::each_year(fields, conferences, range, cli, |year, results| {
+        let report = finish_results(subject.clone(), results, cli.matching());
+        let mut individuals: Vec<_> = report
+            .individuals
+            .iter()
+            .filter(|individual| is_ours(&individual.school))
+            .collect();
+        individuals.sort_by_key(|individual| std::cmp::Reverse(individual.score));
+        let mut scores: Vec<Score> = individuals
+            .iter()
+            .map(|individual| individual.score)
+            .collect();
+        scores.sort();
+
+        points.push(Point {
+            year,
+            team_score: report
+                .teams
+                .iter()
+                .filter(|team| is_ours(&team.school))
+                .map(|team| team.score)
+                .max(),
+            best_individual: individuals.first().map(|individual| individual.score),
+            best_name: individuals
+                .first()
+                .map(|individual| individual.name.clone()),
+            median: Score::median(&scores),
+            advancers: individuals
+                .iter()
+                .filter(|individual| individual.advance.is_some())
+                .count(),
+        });
+    });
+
+    if points.is_empty() {
+        eprintln!("{}", "Didn't return any results".red());
+        return;
+    }
+    let title = match school {
+        Some(school) => format!("{school} {} Trend:", subject.to_string()),
+        None => format!("{} Trend:", subject.to_string()),
+    };
+
+    if cli.format == Format::Csv {
+        match write_csv(&subject, &points, cli) {
+            Ok(path) => eprintln!("Wrote {}", path.display()),
+            Err(err) => eprintln!("{}", format!("Couldn't write CSV: {err}").red()),
+        }
+        return;
+    }
+    emit_summary("Trends", &points, &points, cli, || {
+        print(&title, &points, cli)
+    });
+}
+
+/// Writes one row per year to `{subject}_trend.csv` in `--output`
+fn write_csv(subject: &Subject, points: &[Point], cli: &Cli) -> Result<PathBuf, String> {
+    let directory = PathBuf::from(cli.output.as_deref().unwrap_or("."));
+    std::fs::create_dir_all(&directory).map_err(|err| err.to_string())?;
+    let path = directory.join(format!("{}_trend.csv", file_stem(subject)));
+
+    let mut writer = csv::Writer::from_path(&path).map_err(|err| err.to_string())?;
+    for point in points {
+        writer.serialize(point).map_err(|err| err.to_string())?;
+    }
+    writer.flush().map_err(|err| err.to_string())?;
+    Ok(path)
+}
+
+fn print(title: &str, points: &[Point], cli: &Cli) {
+    println!("{title}");
+
+    if cli.plain {
+        for point in points {
+            let mut fields = vec![point.year.to_string()];
+            if let Some(score) = point.team_score {
+                fields.push(format!("team score: {score}"));
+            }
+            if let Some(score) = point.best_individual {
+                fields.push(format!(
+                    "best individual: {score} ({})",
+                    point.best_name.clone().unwrap_or_default()
+                ));
+            }
+            if let Some(score) = point.median {
+                fields.push(format!("median: {score}"));
+            }
+            fields.push(format!("advancers: {}", point.advancers));
+            println!("{}", fields.join("; "));
+        }
+        return;
+    }
+
+    let headers = [
+        "Year",
+        "Team",
+        "Best",
+        "Median",
+        "Advancers",
+        "Best Individual",
+    ];
+    let rows: Vec<Vec<String>> = points
+        .iter()
+        .map(|point| {
+            vec![
+                point.year.to_string(),
+                optional(point.team_score),
+                optional(point.best_individual),
+                optional(point.median),
+                point.advancers.to_string(),
+                point.best_name.clone().unwrap_or_default(),
+            ]
+        })
+        .collect();
+    print_aligned(&headers, &rows, 5);
+
+    println!();
+    let lines = [
+        (
+            "Team",
+            points
+                .iter()
+                .map(|point| point.team_score.map(Score::as_f64))
+                .collect::<Vec<_>>(),
+        ),
+        (
+            "Best",
+            points
+                .iter()
+                .map(|point| point.best_individual.map(Score::as_f64))
+                .collect(),
+        ),
+        (
+            "Median",
+            points
+                .iter()
+                .map(|point| point.median.map(Score::as_f64))
+                .collect(),
+        ),
+        (
+            "Advancers",
+            points
+                .iter()
+                .map(|point| Some(point.advancers as f64))
+                .collect(),
+        ),
+    ];
+    let first = points.first().map(|point| point.year).unwrap_or_default();
+    let last = points.last().map(|point| point.year).unwrap_or_default();
+    for (label, values) in lines {
+        if values.iter().all(Option::is_none) {
+            continue;
+        }
+        println!("{label:9}  {first} {} {last}", sparkline(&values).cyan());
+    }
+}
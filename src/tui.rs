@@ -0,0 +1,484 @@
+use std::collections::HashMap;
+
+use colored::Color as TermColor;
+use ratatui::{
+    DefaultTerminal, Frame,
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Cell as TuiCell, Paragraph, Row as TuiRow, Table, TableState},
+};
+
+use crate::{
+    conference,
+    output::{IndividualRecord, Medal, Report, TeamRecord, optional},
+    request::{Level, RequestFields},
+    sort::SortKey,
+    theme::theme,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum View {
+    Individuals,
+    Teams,
+}
+
+#[derive(Default)]
+struct Filters {
+    conference: Option<u8>,
+    district: Option<u8>,
+    region: Option<u8>,
+    school: String,
+}
+
+impl Filters {
+    fn keep(&self, conference: u8, district: Option<u8>, region: Option<u8>, school: &str) -> bool {
+        self.conference.is_none_or(|code| code == conference)
+            && self.district.is_none_or(|number| district == Some(number))
+            && self.region.is_none_or(|number| region == Some(number))
+            && school.to_lowercase().contains(&self.school.to_lowercase())
+    }
+}
+
+struct App {
+    /// Every level that's been scraped so far, so each is only scraped once
+    reports: HashMap<Level, Option<Report>>,
+    /// The level asked for on the command line, used when jumping back to it
+    requested: Level,
+    level: Level,
+    view: View,
+    /// Index into the report's subject parts (Science sub-scores), or the total
+    part: Option<usize>,
+    filters: Filters,
+    editing_school: bool,
+    table: TableState,
+    sort: Vec<SortKey>,
+    message: Option<String>,
+}
+
+/// The request for the same results at a different level
+pub fn fields_for(fields: &RequestFields, level: Level) -> RequestFields {
+    let mut fields = fields.clone();
+    (fields.district, fields.region, fields.state) = match level {
+        Level::District(district) => (Some(district), None, false),
+        Level::Region(region) => (None, Some(region), false),
+        Level::State => (None, None, true),
+    };
+    fields
+}
+
+fn level_label(level: Level) -> String {
+    match level {
+        Level::District(0) => String::from("All districts"),
+        Level::Region(0) => String::from("All regions"),
+        level => level.to_string(),
+    }
+}
+
+/// The next option after `current`, wrapping around to no filter at the end
+fn cycle(current: Option<u8>, options: &[u8]) -> Option<u8> {
+    match current {
+        None => options.first().copied(),
+        Some(current) => options.iter().copied().find(|option| *option > current),
+    }
+}
+
+fn color(color: TermColor) -> Color {
+    match color {
+        TermColor::Black => Color::Black,
+        TermColor::Red => Color::Red,
+        TermColor::Green => Color::Green,
+        TermColor::Yellow => Color::Yellow,
+        TermColor::Blue => Color::Blue,
+        TermColor::Magenta => Color::Magenta,
+        TermColor::Cyan => Color::Cyan,
+        TermColor::White => Color::Gray,
+        TermColor::BrightBlack => Color::DarkGray,
+        TermColor::BrightRed => Color::LightRed,
+        TermColor::BrightGreen => Color::LightGreen,
+        TermColor::BrightYellow => Color::LightYellow,
+        TermColor::BrightBlue => Color::LightBlue,
+        TermColor::BrightMagenta => Color::LightMagenta,
+        TermColor::BrightCyan => Color::LightCyan,
+        TermColor::BrightWhite => Color::White,
+        TermColor::TrueColor { r, g, b } => Color::Rgb(r, g, b),
+    }
+}
+
+/// A theme color, or nothing when colors are turned off
+fn style(foreground: Option<TermColor>) -> Style {
+    match foreground {
+        Some(foreground) if colored::control::SHOULD_COLORIZE.should_colorize() => {
+            Style::default().fg(color(foreground))
+        }
+        _ => Style::default(),
+    }
+}
+
+fn medal_style(place: usize) -> Style {
+    match Medal::from_place(place) {
+        Some(medal) if colored::control::SHOULD_COLORIZE.should_colorize() => Style::default()
+            .fg(Color::Black)
+            .bg(color(theme().medal_color(medal))),
+        _ => Style::default(),
+    }
+}
+
+fn individual_row(record: &IndividualRecord) -> TuiRow<'static> {
+    let badge = record.badge.clone();
+    TuiRow::new(vec![
+        TuiCell::from(record.place.to_string()),
+        TuiCell::from(record.name.clone()),
+        TuiCell::from(record.school.clone()),
+        TuiCell::from(record.conference.clone())
+            .style(style(theme().conference_color(record.conference_code))),
+        TuiCell::from(optional(record.district)),
+        TuiCell::from(optional(record.region)),
+        TuiCell::from(record.score.to_string()),
+        match badge {
+            Some((label, badge_color)) => TuiCell::from(label).style(style(Some(badge_color))),
+            None => TuiCell::from(""),
+        },
+    ])
+    .style(medal_style(record.place))
+}
+
+fn team_row(record: &TeamRecord) -> TuiRow<'static> {
+    let badge = record.badge.clone();
+    TuiRow::new(vec![
+        TuiCell::from(record.place.to_string()),
+        TuiCell::from(record.school.clone()),
+        TuiCell::from(record.conference.clone())
+            .style(style(theme().conference_color(record.conference_code))),
+        TuiCell::from(optional(record.district)),
+        TuiCell::from(optional(record.region)),
+        TuiCell::from(record.score.to_string()),
+        match badge {
+            Some((label, badge_color)) => TuiCell::from(label).style(style(Some(badge_color))),
+            None => TuiCell::from(""),
+        },
+    ])
+    .style(medal_style(record.place))
+}
+
+impl App {
+    fn new(report: Report, level: Level, sort: Vec<SortKey>) -> Self {
+        let mut reports = HashMap::new();
+        reports.insert(level, Some(report));
+        let mut table = TableState::default();
+        table.select(Some(0));
+        Self {
+            reports,
+            requested: level,
+            level,
+            view: View::Individuals,
+            part: None,
+            filters: Filters::default(),
+            editing_school: false,
+            table,
+            sort,
+            message: None,
+        }
+    }
+
+    fn report(&self) -> Option<&Report> {
+        self.reports.get(&self.level)?.as_ref()
+    }
+
+    /// Individual rows in the same order as the terminal tables, after filtering
+    fn individuals(&self) -> Vec<IndividualRecord> {
+        let Some(report) = self.report() else {
+            return Vec::new();
+        };
+        let individuals = match self.part {
+            Some(part) => &report.parts[part].1,
+            None => &report.individuals,
+        };
+        report
+            .individual_records(individuals, &None, &self.sort)
+            .into_iter()
+            .filter(|record| {
+                self.filters.keep(
+                    record.conference_code,
+                    record.district,
+                    record.region,
+                    &record.school,
+                )
+            })
+            .collect()
+    }
+
+    /// Team rows in the same order as the terminal tables, after filtering
+    fn teams(&self) -> Vec<TeamRecord> {
+        let Some(report) = self.report() else {
+            return Vec::new();
+        };
+        report
+            .team_records(&None, &self.sort)
+            .into_iter()
+            .filter(|record| {
+                self.filters.keep(
+                    record.conference_code,
+                    record.district,
+                    record.region,
+                    &record.school,
+                )
+            })
+            .collect()
+    }
+
+    fn row_count(&self) -> usize {
+        match self.view {
+            View::Individuals => self.individuals().len(),
+            View::Teams => self.teams().len(),
+        }
+    }
+
+    /// Every value of one column in the current level, for cycling through filters
+    fn options(&self, column: fn(u8, Option<u8>, Option<u8>) -> Option<u8>) -> Vec<u8> {
+        let Some(report) = self.report() else {
+            return Vec::new();
+        };
+        let mut options: Vec<u8> = report
+            .individual_records(&report.individuals, &None, &[])
+            .iter()
+            .filter_map(|record| column(record.conference_code, record.district, record.region))
+            .chain(report.team_records(&None, &[]).iter().filter_map(|record| {
+                column(record.conference_code, record.district, record.region)
+            }))
+            .collect();
+        options.sort();
+        options.dedup();
+        options
+    }
+
+    fn jump(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        level: Level,
+        load: &mut impl FnMut(Level) -> Option<Report>,
+    ) -> std::io::Result<()> {
+        let level = match (level, self.requested) {
+            (Level::District(_), Level::District(district)) => Level::District(district),
+            (Level::Region(_), Level::Region(region)) => Level::Region(region),
+            (level, _) => level,
+        };
+        if !self.reports.contains_key(&level) {
+            self.message = Some(format!("Loading {}...", level_label(level)));
+            terminal.draw(|frame| self.draw(frame))?;
+            let report = load(level);
+            // Progress messages may have been written over the screen
+            terminal.clear()?;
+            self.reports.insert(level, report);
+        }
+        self.message = match self.reports.get(&level) {
+            Some(Some(_)) => None,
+            _ => Some(format!("No results for {}", level_label(level))),
+        };
+        self.level = level;
+        self.part = None;
+        self.filters.district = None;
+        self.filters.region = None;
+        self.table.select(Some(0));
+        Ok(())
+    }
+
+    fn run(
+        mut self,
+        terminal: &mut DefaultTerminal,
+        load: &mut impl FnMut(Level) -> Option<Report>,
+    ) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            if self.editing_school {
+                match key.code {
+                    KeyCode::Enter | KeyCode::Esc => self.editing_school = false,
+                    KeyCode::Backspace => {
+                        self.filters.school.pop();
+                    }
+                    KeyCode::Char(c) => self.filters.school.push(c),
+                    _ => {}
+                }
+                self.table.select(Some(0));
+                continue;
+            }
+
+            let rows = self.row_count();
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+                KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+                KeyCode::PageDown => self.table.scroll_down_by(20),
+                KeyCode::PageUp => self.table.scroll_up_by(20),
+                KeyCode::Home | KeyCode::Char('g') => self.table.select_first(),
+                KeyCode::End | KeyCode::Char('G') => {
+                    self.table.select(Some(rows.saturating_sub(1)))
+                }
+                KeyCode::Tab => {
+                    self.view = match self.view {
+                        View::Individuals => View::Teams,
+                        View::Teams => View::Individuals,
+                    };
+                    self.table.select(Some(0));
+                }
+                KeyCode::Char('p') => {
+                    let parts = self.report().map_or(0, |report| report.parts.len());
+                    self.part = match self.part {
+                        None if parts > 0 => Some(0),
+                        Some(part) if part + 1 < parts => Some(part + 1),
+                        _ => None,
+                    };
+                    self.view = View::Individuals;
+                    self.table.select(Some(0));
+                }
+                KeyCode::Char('c') => {
+                    let options =
+                        self.options(|conference, _, _| (conference != 0).then_some(conference));
+                    self.filters.conference = cycle(self.filters.conference, &options);
+                    self.table.select(Some(0));
+                }
+                KeyCode::Char('d') => {
+                    let options = self.options(|_, district, _| district);
+                    self.filters.district = cycle(self.filters.district, &options);
+                    self.table.select(Some(0));
+                }
+                KeyCode::Char('r') => {
+                    let options = self.options(|_, _, region| region);
+                    self.filters.region = cycle(self.filters.region, &options);
+                    self.table.select(Some(0));
+                }
+                KeyCode::Char('/') | KeyCode::Char('s') => self.editing_school = true,
+                KeyCode::Char('x') => {
+                    self.filters = Filters::default();
+                    self.table.select(Some(0));
+                }
+                KeyCode::Char('1') => self.jump(terminal, Level::District(0), load)?,
+                KeyCode::Char('2') => self.jump(terminal, Level::Region(0), load)?,
+                KeyCode::Char('3') => self.jump(terminal, Level::State, load)?,
+                _ => {}
+            }
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let subject = self.report().map_or(String::new(), |report| {
+            report.subject.to_string().to_string()
+        });
+        let table_name = match (self.view, self.part) {
+            (View::Teams, _) => String::from("Team Scores"),
+            (View::Individuals, None) => String::from("Individual Total Scores"),
+            (View::Individuals, Some(part)) => self.report().map_or(String::new(), |report| {
+                format!("Individual {} Scores", report.parts[part].0.name())
+            }),
+        };
+        let year = self
+            .report()
+            .and_then(|report| report.sources.first())
+            .map(|source| source.year);
+        let filter = |value: Option<String>| value.unwrap_or(String::from("all"));
+        let school = if self.editing_school {
+            format!("{}_", self.filters.school)
+        } else if self.filters.school.is_empty() {
+            String::from("all")
+        } else {
+            self.filters.school.clone()
+        };
+        let lines = vec![
+            Line::from(format!(
+                "{subject} {} - {} - {table_name}",
+                year.map(|year| year.to_string()).unwrap_or_default(),
+                level_label(self.level)
+            ))
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+            Line::from(format!(
+                "Conference: {}  District: {}  Region: {}  School: {school}",
+                filter(
+                    self.filters
+                        .conference
+                        .map(|code| { conference::label(year.unwrap_or_default(), code) })
+                ),
+                filter(self.filters.district.map(|district| district.to_string())),
+                filter(self.filters.region.map(|region| region.to_string())),
+            )),
+        ];
+        frame.render_widget(Paragraph::new(lines), header);
+
+        let (columns, rows, widths): (Vec<&str>, Vec<TuiRow>, Vec<Constraint>) = match self.view {
+            View::Individuals => (
+                vec![
+                    "Place", "Name", "School", "Conf", "District", "Region", "Score", "Advance",
+                ],
+                self.individuals().iter().map(individual_row).collect(),
+                vec![
+                    Constraint::Length(5),
+                    Constraint::Fill(2),
+                    Constraint::Fill(2),
+                    Constraint::Length(5),
+                    Constraint::Length(8),
+                    Constraint::Length(6),
+                    Constraint::Length(8),
+                    Constraint::Length(9),
+                ],
+            ),
+            View::Teams => (
+                vec![
+                    "Place", "School", "Conf", "District", "Region", "Score", "Advance",
+                ],
+                self.teams().iter().map(team_row).collect(),
+                vec![
+                    Constraint::Length(5),
+                    Constraint::Fill(3),
+                    Constraint::Length(5),
+                    Constraint::Length(8),
+                    Constraint::Length(6),
+                    Constraint::Length(8),
+                    Constraint::Length(9),
+                ],
+            ),
+        };
+        let table = Table::new(rows, widths)
+            .header(TuiRow::new(columns).style(Style::default().add_modifier(Modifier::BOLD)))
+            .block(Block::default().borders(Borders::TOP))
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(table, body, &mut self.table);
+
+        let help = match &self.message {
+            Some(message) => message.clone(),
+            None if self.editing_school => String::from("Type a school, Enter to finish"),
+            None => String::from(
+                "q quit  ↑↓ move  Tab individuals/teams  p parts  c/d/r conference/district/region  / school  x clear  1/2/3 district/region/state",
+            ),
+        };
+        frame.render_widget(Paragraph::new(help), footer);
+    }
+}
+
+/// Browses a report in the terminal. `load` scrapes the same results at another level.
+pub fn run(
+    report: Report,
+    level: Option<Level>,
+    sort: Vec<SortKey>,
+    mut load: impl FnMut(Level) -> Option<Report>,
+) -> std::io::Result<()> {
+    let mut terminal = ratatui::init();
+    let result =
+        App::new(report, level.unwrap_or(Level::State), sort).run(&mut terminal, &mut load);
+    ratatui::restore();
+    result
+}